@@ -0,0 +1,185 @@
+use std::ffi::CString;
+use std::ptr;
+
+use super::Filter;
+use crate::codec::Parameters;
+use crate::ffi::*;
+use crate::packet::{self, Mut};
+use crate::{Error, Packet, Rational};
+
+/// A bitstream filter instance (`AVBSFContext`).
+///
+/// Configure the input side with [`set_parameters`](Self::set_parameters) and
+/// [`set_time_base`](Self::set_time_base), call [`init`](Self::init), then
+/// drive it like a codec: [`send_packet`](Self::send_packet) until it reports
+/// `EAGAIN`, [`receive_packet`](Self::receive_packet) until it reports `EAGAIN`
+/// (or `Eof` after [`send_eof`](Self::send_eof)). The output side, which a
+/// muxer needs for its stream, is available from
+/// [`parameters`](Self::parameters) and [`time_base`](Self::time_base) once
+/// initialized.
+pub struct Context {
+    ptr: *mut AVBSFContext,
+}
+
+unsafe impl Send for Context {}
+
+impl Context {
+    pub unsafe fn wrap(ptr: *mut AVBSFContext) -> Self {
+        Context { ptr }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVBSFContext {
+        self.ptr as *const _
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVBSFContext {
+        self.ptr
+    }
+}
+
+impl Context {
+    pub fn new(filter: &Filter) -> Result<Self, Error> {
+        unsafe {
+            let mut ptr = ptr::null_mut();
+
+            match av_bsf_alloc(filter.as_ptr(), &mut ptr) {
+                0 => Ok(Context::wrap(ptr)),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Looks a filter up by name, e.g. `"h264_mp4toannexb"`.
+    pub fn by_name(name: &str) -> Result<Self, Error> {
+        match super::find(name) {
+            Some(filter) => Self::new(&filter),
+            None => Err(Error::BsfNotFound),
+        }
+    }
+
+    /// Parses a filter chain description such as
+    /// `"h264_mp4toannexb,dump_extra=freq=keyframe"` into a single context.
+    /// An empty description yields the pass-through `null` filter.
+    pub fn parse(description: &str) -> Result<Self, Error> {
+        let description = CString::new(description).map_err(|_| Error::Other { errno: EINVAL })?;
+
+        unsafe {
+            let mut ptr = ptr::null_mut();
+
+            match av_bsf_list_parse_str(description.as_ptr(), &mut ptr) {
+                0 => Ok(Context::wrap(ptr)),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    pub fn filter(&self) -> Filter {
+        unsafe { Filter::wrap((*self.as_ptr()).filter) }
+    }
+
+    /// Copies `parameters` into the filter's input parameters. Must be called
+    /// before [`init`](Self::init).
+    pub fn set_parameters<P: Into<Parameters>>(&mut self, parameters: P) -> Result<(), Error> {
+        let parameters = parameters.into();
+
+        unsafe {
+            match avcodec_parameters_copy((*self.as_mut_ptr()).par_in, parameters.as_ptr()) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Sets the time base of the packets that will be sent. Must be called
+    /// before [`init`](Self::init).
+    pub fn set_time_base<R: Into<Rational>>(&mut self, value: R) {
+        unsafe {
+            (*self.as_mut_ptr()).time_base_in = value.into().into();
+        }
+    }
+
+    pub fn init(&mut self) -> Result<(), Error> {
+        unsafe {
+            match av_bsf_init(self.as_mut_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Returns a copy of the output parameters, to be set on the output
+    /// stream. Only meaningful after [`init`](Self::init).
+    pub fn parameters(&self) -> Result<Parameters, Error> {
+        let mut parameters = Parameters::new();
+
+        unsafe {
+            match avcodec_parameters_copy(parameters.as_mut_ptr(), (*self.as_ptr()).par_out) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(parameters),
+            }
+        }
+    }
+
+    /// The time base of the packets returned by
+    /// [`receive_packet`](Self::receive_packet). Only meaningful after
+    /// [`init`](Self::init).
+    pub fn time_base(&self) -> Rational {
+        unsafe { Rational::from((*self.as_ptr()).time_base_out) }
+    }
+
+    /// Submits a packet for filtering. The filter takes a new reference to
+    /// the packet's data, so `packet` is left untouched.
+    pub fn send_packet<P: packet::Ref>(&mut self, packet: &P) -> Result<(), Error> {
+        let mut owned = Packet::empty();
+
+        unsafe {
+            match av_packet_ref(owned.as_mut_ptr(), packet.as_ptr()) {
+                0 => (),
+                e => return Err(Error::from(e)),
+            }
+
+            match av_bsf_send_packet(self.as_mut_ptr(), owned.as_mut_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Signals end of stream; buffered packets can then be drained with
+    /// [`receive_packet`](Self::receive_packet) until it returns `Eof`.
+    pub fn send_eof(&mut self) -> Result<(), Error> {
+        unsafe {
+            match av_bsf_send_packet(self.as_mut_ptr(), ptr::null_mut()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    pub fn receive_packet<P: packet::Mut>(&mut self, packet: &mut P) -> Result<(), Error> {
+        unsafe {
+            av_packet_unref(packet.as_mut_ptr());
+
+            match av_bsf_receive_packet(self.as_mut_ptr(), packet.as_mut_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Drops any buffered packets and resets the filter state, e.g. after a
+    /// seek.
+    pub fn flush(&mut self) {
+        unsafe {
+            av_bsf_flush(self.as_mut_ptr());
+        }
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe {
+            av_bsf_free(&mut self.ptr);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::ffi::CStr;
+use std::str::from_utf8_unchecked;
+
+use crate::codec::Id;
+use crate::ffi::*;
+
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub struct Filter {
+    ptr: *const AVBitStreamFilter,
+}
+
+unsafe impl Send for Filter {}
+unsafe impl Sync for Filter {}
+
+impl Filter {
+    pub unsafe fn wrap(ptr: *const AVBitStreamFilter) -> Self {
+        Filter { ptr }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVBitStreamFilter {
+        self.ptr
+    }
+}
+
+impl Filter {
+    pub fn name(&self) -> &str {
+        unsafe { from_utf8_unchecked(CStr::from_ptr((*self.as_ptr()).name).to_bytes()) }
+    }
+
+    /// The codecs this filter accepts, or `None` if it accepts any codec.
+    pub fn codecs(&self) -> Option<CodecIter> {
+        unsafe {
+            let ptr = (*self.as_ptr()).codec_ids;
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(CodecIter::new(ptr))
+            }
+        }
+    }
+
+    pub fn supports(&self, id: Id) -> bool {
+        self.codecs().is_none_or(|mut ids| ids.any(|i| i == id))
+    }
+}
+
+pub struct CodecIter {
+    ptr: *const AVCodecID,
+}
+
+impl CodecIter {
+    pub fn new(ptr: *const AVCodecID) -> Self {
+        CodecIter { ptr }
+    }
+}
+
+impl Iterator for CodecIter {
+    type Item = Id;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            if *self.ptr == AVCodecID::AV_CODEC_ID_NONE {
+                return None;
+            }
+
+            let id = Id::from(*self.ptr);
+            self.ptr = self.ptr.offset(1);
+
+            Some(id)
+        }
+    }
+}
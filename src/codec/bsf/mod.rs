@@ -0,0 +1,62 @@
+pub mod filter;
+pub use self::filter::Filter;
+
+pub mod context;
+pub use self::context::Context;
+
+use std::ffi::CString;
+use std::ptr;
+
+use crate::ffi::*;
+use libc::c_void;
+
+pub fn find(name: &str) -> Option<Filter> {
+    unsafe {
+        let name = CString::new(name).ok()?;
+        let ptr = av_bsf_get_by_name(name.as_ptr());
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Filter::wrap(ptr))
+        }
+    }
+}
+
+pub fn list() -> FilterIter {
+    FilterIter::new()
+}
+
+pub struct FilterIter {
+    opaque: *mut c_void,
+}
+
+impl FilterIter {
+    pub fn new() -> Self {
+        FilterIter {
+            opaque: ptr::null_mut(),
+        }
+    }
+}
+
+impl Default for FilterIter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for FilterIter {
+    type Item = Filter;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            let ptr = av_bsf_iterate(&mut self.opaque);
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Filter::wrap(ptr))
+            }
+        }
+    }
+}
@@ -4,11 +4,11 @@ use std::sync::Arc;
 
 use super::decoder::Decoder;
 use super::encoder::Encoder;
-use super::{Compliance, Debug, Flags, Id, Parameters, threading};
+use super::{Compliance, Debug, Flags, Flags2, Id, Parameters, threading};
 use crate::ffi::*;
 use crate::media;
-use crate::{Codec, Error, Rational};
-use libc::c_int;
+use crate::{Codec, Error, Rational, option};
+use libc::{c_int, c_void};
 
 pub struct Context {
     ptr: *mut AVCodecContext,
@@ -95,6 +95,16 @@ impl Context {
         }
     }
 
+    pub fn flags2(&self) -> Flags2 {
+        unsafe { Flags2::from_bits_truncate((*self.as_ptr()).flags2) }
+    }
+
+    pub fn set_flags2(&mut self, value: Flags2) {
+        unsafe {
+            (*self.as_mut_ptr()).flags2 = value.bits();
+        }
+    }
+
     pub fn id(&self) -> Id {
         unsafe { Id::from((*self.as_ptr()).codec_id) }
     }
@@ -176,6 +186,20 @@ impl Default for Context {
     }
 }
 
+unsafe impl option::Target for Context {
+    fn as_ptr(&self) -> *const c_void {
+        self.ptr as *const _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        self.ptr as *mut _
+    }
+}
+
+impl option::Settable for Context {}
+impl option::Gettable for Context {}
+impl option::Iterable for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
@@ -1,6 +1,8 @@
 use std::ops::{Deref, DerefMut};
 use std::ptr;
 
+#[cfg(feature = "ffmpeg_4_3")]
+use super::ExportData;
 use super::{Audio, Check, Conceal, Opened, Subtitle, Video};
 use crate::codec::{Context, traits};
 use crate::ffi::*;
@@ -107,6 +109,20 @@ impl Decoder {
         }
     }
 
+    #[cfg(feature = "ffmpeg_4_3")]
+    pub fn export_side_data(&self) -> ExportData {
+        unsafe { ExportData::from_bits_truncate((*self.as_ptr()).export_side_data) }
+    }
+
+    /// Sets the side data the decoder attaches to frames on top of what it
+    /// always does, such as motion vectors.
+    #[cfg(feature = "ffmpeg_4_3")]
+    pub fn set_export_side_data(&mut self, value: ExportData) {
+        unsafe {
+            (*self.as_mut_ptr()).export_side_data = value.bits();
+        }
+    }
+
     pub fn skip_loop_filter(&mut self, value: Discard) {
         unsafe {
             (*self.as_mut_ptr()).skip_loop_filter = value.into();
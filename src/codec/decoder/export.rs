@@ -0,0 +1,15 @@
+use crate::ffi::*;
+use libc::c_int;
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct ExportData: c_int {
+        const MVS  = AV_CODEC_EXPORT_DATA_MVS;
+        const PRFT = AV_CODEC_EXPORT_DATA_PRFT;
+        const VIDEO_ENC_PARAMS = AV_CODEC_EXPORT_DATA_VIDEO_ENC_PARAMS;
+        #[cfg(feature = "ffmpeg_4_4")]
+        const FILM_GRAIN = AV_CODEC_EXPORT_DATA_FILM_GRAIN;
+        #[cfg(feature = "ffmpeg_7_1")]
+        const ENHANCEMENTS = AV_CODEC_EXPORT_DATA_ENHANCEMENTS;
+    }
+}
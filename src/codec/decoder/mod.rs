@@ -18,6 +18,11 @@ pub use self::conceal::Conceal;
 pub mod check;
 pub use self::check::Check;
 
+#[cfg(feature = "ffmpeg_4_3")]
+pub mod export;
+#[cfg(feature = "ffmpeg_4_3")]
+pub use self::export::ExportData;
+
 pub mod opened;
 pub use self::opened::Opened;
 
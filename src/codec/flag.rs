@@ -1,5 +1,5 @@
 use crate::ffi::*;
-use libc::c_uint;
+use libc::{c_int, c_uint};
 
 bitflags! {
     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -23,5 +23,23 @@ bitflags! {
         const LOOP_FILTER     = AV_CODEC_FLAG_LOOP_FILTER;
         const INTERLACED_ME   = AV_CODEC_FLAG_INTERLACED_ME;
         const CLOSED_GOP      = AV_CODEC_FLAG_CLOSED_GOP;
+        #[cfg(feature = "ffmpeg_6_0")]
+        const COPY_OPAQUE     = AV_CODEC_FLAG_COPY_OPAQUE;
+        #[cfg(feature = "ffmpeg_6_0")]
+        const FRAME_DURATION  = AV_CODEC_FLAG_FRAME_DURATION;
+    }
+}
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct Flags2: c_int {
+        const FAST          = AV_CODEC_FLAG2_FAST;
+        const NO_OUTPUT     = AV_CODEC_FLAG2_NO_OUTPUT;
+        const LOCAL_HEADER  = AV_CODEC_FLAG2_LOCAL_HEADER;
+        const IGNORE_CROP   = AV_CODEC_FLAG2_IGNORE_CROP;
+        const SHOW_ALL      = AV_CODEC_FLAG2_SHOW_ALL;
+        const EXPORT_MVS    = AV_CODEC_FLAG2_EXPORT_MVS;
+        const SKIP_MANUAL   = AV_CODEC_FLAG2_SKIP_MANUAL;
+        const RO_FLUSH_NOOP = AV_CODEC_FLAG2_RO_FLUSH_NOOP;
     }
 }
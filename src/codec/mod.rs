@@ -1,11 +1,16 @@
 pub mod flag;
-pub use self::flag::Flags;
+pub use self::flag::{Flags, Flags2};
 
 pub mod id;
 pub use self::id::Id;
 
 pub mod packet;
 
+pub mod bsf;
+
+pub mod parser;
+pub use self::parser::Parser;
+
 pub mod subtitle;
 
 #[cfg(not(feature = "ffmpeg_5_0"))]
@@ -4,6 +4,9 @@ use std::slice;
 
 use super::{Borrow, Flags, Mut, Ref, SideData};
 use crate::ffi::*;
+use crate::util::buffer;
+#[cfg(feature = "ffmpeg_5_0")]
+use crate::util::opaque::{self, Opaque};
 use crate::{Error, Rational, format};
 use libc::c_int;
 
@@ -63,6 +66,61 @@ impl Packet {
         Borrow::new(data)
     }
 
+    /// Wraps `data` without copying it. The vector is dropped once FFmpeg
+    /// releases its last reference to the packet data.
+    ///
+    /// Decoders may read up to `AV_INPUT_BUFFER_PADDING_SIZE` bytes past the
+    /// end of a packet, so that much spare capacity is zeroed, reallocating
+    /// only if the vector does not already have it.
+    pub fn from_vec(mut data: Vec<u8>) -> Result<Self, Error> {
+        let len = data.len();
+        let padding = AV_INPUT_BUFFER_PADDING_SIZE as usize;
+
+        data.reserve(padding);
+        data.spare_capacity_mut()[..padding].fill(mem::MaybeUninit::new(0));
+
+        let ptr = data.as_ptr();
+
+        unsafe { Packet::from_owned_buffer(Box::new(data), ptr, len, true) }
+    }
+
+    /// Wraps `data`, e.g. a `Box<[u8]>` or a `bytes::Bytes`, without copying
+    /// it. `data` is dropped once FFmpeg releases its last reference to the
+    /// packet data, which may happen on another thread.
+    ///
+    /// The packet is read-only; making it writable copies the data. As the
+    /// buffer carries no padding, prefer [`from_vec`](Self::from_vec) for
+    /// packets that are sent to a decoder.
+    pub fn from_buffer<T: AsRef<[u8]> + Send + 'static>(data: T) -> Result<Self, Error> {
+        let data = Box::new(data);
+        let slice = (*data).as_ref();
+        let (ptr, len) = (slice.as_ptr(), slice.len());
+
+        unsafe { Packet::from_owned_buffer(data, ptr, len, false) }
+    }
+
+    unsafe fn from_owned_buffer<T: Send + 'static>(
+        owner: Box<T>,
+        ptr: *const u8,
+        len: usize,
+        writable: bool,
+    ) -> Result<Self, Error> {
+        unsafe {
+            let buf = buffer::create(owner, ptr, len, writable);
+
+            if buf.is_null() {
+                return Err(Error::Other { errno: ENOMEM });
+            }
+
+            let mut packet = Packet::empty();
+            packet.0.buf = buf;
+            packet.0.data = (*buf).data;
+            packet.0.size = len as c_int;
+
+            Ok(packet)
+        }
+    }
+
     #[inline]
     pub fn shrink(&mut self, size: usize) {
         unsafe {
@@ -160,6 +218,22 @@ impl Packet {
         self.0.time_base = value.into();
     }
 
+    /// The user data attached with [`set_opaque`](Self::set_opaque), or by an
+    /// encoder with `codec::Flags::COPY_OPAQUE` set from the frame this
+    /// packet was made from.
+    #[cfg(feature = "ffmpeg_5_0")]
+    pub fn opaque(&self) -> Option<Opaque> {
+        unsafe { opaque::get(self.0.opaque_ref) }
+    }
+
+    /// Attaches user data to the packet, replacing any, for a decoder with
+    /// `codec::Flags::COPY_OPAQUE` set to carry over to the frames it
+    /// decodes from it.
+    #[cfg(feature = "ffmpeg_5_0")]
+    pub fn set_opaque(&mut self, value: Option<Opaque>) -> Result<(), Error> {
+        unsafe { opaque::set(&mut self.0.opaque_ref, value) }
+    }
+
     #[inline]
     pub fn size(&self) -> usize {
         self.0.size as usize
@@ -207,10 +281,14 @@ impl Packet {
         }
     }
 
+    /// Returns `None` if the packet has no data or its data is shared or
+    /// read-only, e.g. after [`from_buffer`](Self::from_buffer).
     #[inline]
     pub fn data_mut(&mut self) -> Option<&mut [u8]> {
         unsafe {
-            if self.0.data.is_null() {
+            if self.0.data.is_null()
+                || (!self.0.buf.is_null() && av_buffer_is_writable(self.0.buf) == 0)
+            {
                 None
             } else {
                 Some(slice::from_raw_parts_mut(self.0.data, self.0.size as usize))
@@ -1,9 +1,16 @@
 use std::any::Any;
+use std::mem;
+use std::ptr;
+use std::slice;
 use std::sync::Arc;
 
-use super::{Context, Id};
+#[cfg(feature = "ffmpeg_7_0")]
+use super::packet::side_data;
+use super::{Context, Id, Profile};
 use crate::ffi::*;
-use crate::media;
+use crate::util::format;
+use crate::{ChannelLayout, FieldOrder, Rational, chroma, color, media};
+use libc::c_int;
 
 pub struct Parameters {
     ptr: *mut AVCodecParameters,
@@ -70,6 +77,386 @@ impl Parameters {
             (*self.as_mut_ptr()).bit_rate = value;
         }
     }
+
+    pub fn codec_tag(&self) -> u32 {
+        unsafe { (*self.as_ptr()).codec_tag }
+    }
+
+    pub fn set_codec_tag(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).codec_tag = value;
+        }
+    }
+
+    pub fn extradata(&self) -> Option<&[u8]> {
+        unsafe {
+            let ptr = (*self.as_ptr()).extradata;
+            let size = (*self.as_ptr()).extradata_size;
+
+            if ptr.is_null() || size <= 0 {
+                None
+            } else {
+                Some(slice::from_raw_parts(ptr, size as usize))
+            }
+        }
+    }
+
+    /// Replaces the extradata with a padded copy of `data`, or removes it if
+    /// `data` is empty.
+    pub fn set_extradata(&mut self, data: &[u8]) {
+        unsafe {
+            let parameters = self.as_mut_ptr();
+
+            av_freep(&mut (*parameters).extradata as *mut *mut u8 as *mut _);
+            (*parameters).extradata_size = 0;
+
+            if data.is_empty() {
+                return;
+            }
+
+            let size = data.len() + AV_INPUT_BUFFER_PADDING_SIZE as usize;
+            let extradata = av_mallocz(size) as *mut u8;
+
+            if extradata.is_null() {
+                panic!("out of memory");
+            }
+
+            ptr::copy_nonoverlapping(data.as_ptr(), extradata, data.len());
+
+            (*parameters).extradata = extradata;
+            (*parameters).extradata_size = data.len() as c_int;
+        }
+    }
+
+    pub fn bits_per_coded_sample(&self) -> i32 {
+        unsafe { (*self.as_ptr()).bits_per_coded_sample }
+    }
+
+    pub fn set_bits_per_coded_sample(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).bits_per_coded_sample = value;
+        }
+    }
+
+    pub fn bits_per_raw_sample(&self) -> i32 {
+        unsafe { (*self.as_ptr()).bits_per_raw_sample }
+    }
+
+    pub fn set_bits_per_raw_sample(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).bits_per_raw_sample = value;
+        }
+    }
+
+    pub fn profile(&self) -> Profile {
+        unsafe { Profile::from((self.id(), (*self.as_ptr()).profile)) }
+    }
+
+    pub fn set_profile(&mut self, value: Profile) {
+        unsafe {
+            (*self.as_mut_ptr()).profile = value.into();
+        }
+    }
+
+    pub fn level(&self) -> i32 {
+        unsafe { (*self.as_ptr()).level }
+    }
+
+    pub fn set_level(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).level = value;
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).width as u32 }
+    }
+
+    pub fn set_width(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).width = value as c_int;
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).height as u32 }
+    }
+
+    pub fn set_height(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).height = value as c_int;
+        }
+    }
+
+    /// The pixel format, for video parameters.
+    pub fn pixel_format(&self) -> format::Pixel {
+        unsafe {
+            match (*self.as_ptr()).format {
+                -1 => format::Pixel::None,
+                value => format::Pixel::from(mem::transmute::<c_int, AVPixelFormat>(value)),
+            }
+        }
+    }
+
+    pub fn set_pixel_format(&mut self, value: format::Pixel) {
+        unsafe {
+            (*self.as_mut_ptr()).format = mem::transmute::<AVPixelFormat, c_int>(value.into());
+        }
+    }
+
+    /// The sample format, for audio parameters.
+    pub fn sample_format(&self) -> format::Sample {
+        unsafe {
+            match (*self.as_ptr()).format {
+                -1 => format::Sample::None,
+                value => format::Sample::from(mem::transmute::<c_int, AVSampleFormat>(value)),
+            }
+        }
+    }
+
+    pub fn set_sample_format(&mut self, value: format::Sample) {
+        unsafe {
+            (*self.as_mut_ptr()).format = mem::transmute::<AVSampleFormat, c_int>(value.into());
+        }
+    }
+
+    pub fn aspect_ratio(&self) -> Rational {
+        unsafe { Rational::from((*self.as_ptr()).sample_aspect_ratio) }
+    }
+
+    pub fn set_aspect_ratio<R: Into<Rational>>(&mut self, value: R) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_aspect_ratio = value.into().into();
+        }
+    }
+
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn frame_rate(&self) -> Option<Rational> {
+        unsafe {
+            let value = Rational::from((*self.as_ptr()).framerate);
+
+            if value.numerator() == 0 {
+                None
+            } else {
+                Some(value)
+            }
+        }
+    }
+
+    #[cfg(feature = "ffmpeg_6_1")]
+    pub fn set_frame_rate<R: Into<Rational>>(&mut self, value: Option<R>) {
+        unsafe {
+            (*self.as_mut_ptr()).framerate = match value {
+                Some(value) => value.into().into(),
+                None => AVRational { num: 0, den: 1 },
+            };
+        }
+    }
+
+    pub fn field_order(&self) -> FieldOrder {
+        unsafe { FieldOrder::from((*self.as_ptr()).field_order) }
+    }
+
+    pub fn set_field_order(&mut self, value: FieldOrder) {
+        unsafe {
+            (*self.as_mut_ptr()).field_order = value.into();
+        }
+    }
+
+    pub fn color_range(&self) -> color::Range {
+        unsafe { color::Range::from((*self.as_ptr()).color_range) }
+    }
+
+    pub fn set_color_range(&mut self, value: color::Range) {
+        unsafe {
+            (*self.as_mut_ptr()).color_range = value.into();
+        }
+    }
+
+    pub fn color_primaries(&self) -> color::Primaries {
+        unsafe { color::Primaries::from((*self.as_ptr()).color_primaries) }
+    }
+
+    pub fn set_color_primaries(&mut self, value: color::Primaries) {
+        unsafe {
+            (*self.as_mut_ptr()).color_primaries = value.into();
+        }
+    }
+
+    pub fn color_transfer_characteristic(&self) -> color::TransferCharacteristic {
+        unsafe { color::TransferCharacteristic::from((*self.as_ptr()).color_trc) }
+    }
+
+    pub fn set_color_transfer_characteristic(&mut self, value: color::TransferCharacteristic) {
+        unsafe {
+            (*self.as_mut_ptr()).color_trc = value.into();
+        }
+    }
+
+    pub fn color_space(&self) -> color::Space {
+        unsafe { color::Space::from((*self.as_ptr()).color_space) }
+    }
+
+    pub fn set_color_space(&mut self, value: color::Space) {
+        unsafe {
+            (*self.as_mut_ptr()).color_space = value.into();
+        }
+    }
+
+    pub fn chroma_location(&self) -> chroma::Location {
+        unsafe { chroma::Location::from((*self.as_ptr()).chroma_location) }
+    }
+
+    pub fn set_chroma_location(&mut self, value: chroma::Location) {
+        unsafe {
+            (*self.as_mut_ptr()).chroma_location = value.into();
+        }
+    }
+
+    pub fn video_delay(&self) -> i32 {
+        unsafe { (*self.as_ptr()).video_delay }
+    }
+
+    pub fn set_video_delay(&mut self, value: i32) {
+        unsafe {
+            (*self.as_mut_ptr()).video_delay = value;
+        }
+    }
+
+    pub fn channels(&self) -> u16 {
+        #[cfg(not(feature = "ffmpeg_7_0"))]
+        unsafe {
+            (*self.as_ptr()).channels as u16
+        }
+
+        #[cfg(feature = "ffmpeg_7_0")]
+        {
+            self.channel_layout().channels() as u16
+        }
+    }
+
+    pub fn channel_layout(&self) -> ChannelLayout {
+        unsafe {
+            #[cfg(not(feature = "ffmpeg_7_0"))]
+            {
+                ChannelLayout::from_bits_truncate((*self.as_ptr()).channel_layout)
+            }
+
+            #[cfg(feature = "ffmpeg_7_0")]
+            {
+                ChannelLayout::from((*self.as_ptr()).ch_layout)
+            }
+        }
+    }
+
+    pub fn set_channel_layout(&mut self, value: ChannelLayout) {
+        unsafe {
+            #[cfg(not(feature = "ffmpeg_7_0"))]
+            {
+                (*self.as_mut_ptr()).channel_layout = value.bits();
+                (*self.as_mut_ptr()).channels = value.channels();
+            }
+
+            #[cfg(feature = "ffmpeg_7_0")]
+            {
+                (*self.as_mut_ptr()).ch_layout = value.into();
+            }
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        unsafe { (*self.as_ptr()).sample_rate as u32 }
+    }
+
+    pub fn set_sample_rate(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).sample_rate = value as c_int;
+        }
+    }
+
+    pub fn block_align(&self) -> u32 {
+        unsafe { (*self.as_ptr()).block_align as u32 }
+    }
+
+    pub fn set_block_align(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).block_align = value as c_int;
+        }
+    }
+
+    /// Number of samples per channel in an audio frame, if constant.
+    pub fn frame_size(&self) -> u32 {
+        unsafe { (*self.as_ptr()).frame_size as u32 }
+    }
+
+    pub fn set_frame_size(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).frame_size = value as c_int;
+        }
+    }
+
+    /// Number of priming samples the decoder discards at the start.
+    pub fn initial_padding(&self) -> u32 {
+        unsafe { (*self.as_ptr()).initial_padding as u32 }
+    }
+
+    pub fn set_initial_padding(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).initial_padding = value as c_int;
+        }
+    }
+
+    /// Number of padding samples at the end of the stream.
+    pub fn trailing_padding(&self) -> u32 {
+        unsafe { (*self.as_ptr()).trailing_padding as u32 }
+    }
+
+    pub fn set_trailing_padding(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).trailing_padding = value as c_int;
+        }
+    }
+
+    /// Number of samples to skip after a discontinuity.
+    pub fn seek_preroll(&self) -> u32 {
+        unsafe { (*self.as_ptr()).seek_preroll as u32 }
+    }
+
+    pub fn set_seek_preroll(&mut self, value: u32) {
+        unsafe {
+            (*self.as_mut_ptr()).seek_preroll = value as c_int;
+        }
+    }
+
+    /// The stream-global side data of type `kind` (`coded_side_data`), which
+    /// muxers write to the container header.
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub fn side_data(&self, kind: side_data::Type) -> Option<&[u8]> {
+        unsafe {
+            let ptr = av_packet_side_data_get(
+                (*self.as_ptr()).coded_side_data,
+                (*self.as_ptr()).nb_coded_side_data,
+                kind.into(),
+            );
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(slice::from_raw_parts((*ptr).data, (*ptr).size))
+            }
+        }
+    }
+
+    #[cfg(feature = "ffmpeg_7_0")]
+    pub fn remove_side_data(&mut self, kind: side_data::Type) {
+        unsafe {
+            av_packet_side_data_remove(
+                (*self.as_mut_ptr()).coded_side_data,
+                &mut (*self.as_mut_ptr()).nb_coded_side_data,
+                kind.into(),
+            );
+        }
+    }
 }
 
 impl Default for Parameters {
@@ -129,4 +516,67 @@ mod tests {
         assert_eq!(parameters.id(), Id::WEBVTT);
         assert_eq!(parameters.bit_rate(), 64_000);
     }
+
+    #[test]
+    fn sets_video_parameters() {
+        let mut parameters = Parameters::new();
+        parameters.set_medium(media::Type::Video);
+        parameters.set_id(Id::H264);
+        parameters.set_width(1920);
+        parameters.set_height(1080);
+        parameters.set_pixel_format(format::Pixel::YUV420P);
+        parameters.set_aspect_ratio((1, 1));
+        parameters.set_field_order(FieldOrder::Progressive);
+        parameters.set_color_range(color::Range::MPEG);
+        parameters.set_level(41);
+
+        let copy = parameters.clone();
+        assert_eq!(copy.width(), 1920);
+        assert_eq!(copy.height(), 1080);
+        assert_eq!(copy.pixel_format(), format::Pixel::YUV420P);
+        assert_eq!(copy.aspect_ratio(), Rational(1, 1));
+        assert_eq!(copy.field_order(), FieldOrder::Progressive);
+        assert_eq!(copy.color_range(), color::Range::MPEG);
+        assert_eq!(copy.level(), 41);
+    }
+
+    #[test]
+    fn sets_audio_parameters() {
+        let mut parameters = Parameters::new();
+        parameters.set_medium(media::Type::Audio);
+        parameters.set_id(Id::AAC);
+        parameters.set_sample_format(format::Sample::F32(format::sample::Type::Planar));
+        parameters.set_sample_rate(48_000);
+        parameters.set_channel_layout(ChannelLayout::STEREO);
+        parameters.set_frame_size(1024);
+        parameters.set_initial_padding(1024);
+
+        assert_eq!(
+            parameters.sample_format(),
+            format::Sample::F32(format::sample::Type::Planar)
+        );
+        assert_eq!(parameters.sample_rate(), 48_000);
+        assert_eq!(parameters.channels(), 2);
+        assert_eq!(parameters.channel_layout(), ChannelLayout::STEREO);
+        assert_eq!(parameters.frame_size(), 1024);
+        assert_eq!(parameters.initial_padding(), 1024);
+    }
+
+    #[test]
+    fn replaces_extradata() {
+        let mut parameters = Parameters::new();
+        assert_eq!(parameters.extradata(), None);
+
+        parameters.set_extradata(&[0x12, 0x10]);
+        assert_eq!(parameters.extradata(), Some(&[0x12u8, 0x10][..]));
+
+        // Copies carry their own extradata.
+        let copy = parameters.clone();
+        parameters.set_extradata(&[0x11, 0x90, 0x56, 0xe5]);
+        assert_eq!(copy.extradata(), Some(&[0x12u8, 0x10][..]));
+        assert_eq!(parameters.extradata().map(<[u8]>::len), Some(4));
+
+        parameters.set_extradata(&[]);
+        assert_eq!(parameters.extradata(), None);
+    }
 }
@@ -0,0 +1,286 @@
+use std::mem;
+use std::ptr;
+
+use super::field_order::FieldOrder;
+use super::packet::Flags;
+use super::{Context, Id};
+use crate::ffi::*;
+use crate::{Error, Packet, format, picture};
+use libc::c_int;
+
+/// Splits a raw elementary stream (`AVCodecParserContext`) into packets that
+/// each hold exactly one frame, ready for `decoder::Opened::send_packet`.
+///
+/// Feed arbitrary chunks of input with [`parse`](Self::parse) and collect the
+/// packets it yields; at end of stream, drain the last buffered frame with
+/// [`flush`](Self::flush). The accessors describe the most recently returned
+/// packet as seen by the parser, without decoding it.
+pub struct Parser {
+    ptr: *mut AVCodecParserContext,
+    context: Context,
+}
+
+unsafe impl Send for Parser {}
+
+impl Parser {
+    pub unsafe fn as_ptr(&self) -> *const AVCodecParserContext {
+        self.ptr as *const _
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVCodecParserContext {
+        self.ptr
+    }
+}
+
+impl Parser {
+    /// Returns `None` if libavcodec has no parser for `id`.
+    pub fn new(id: Id) -> Option<Self> {
+        unsafe {
+            let ptr = av_parser_init(AVCodecID::from(id) as c_int);
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Parser {
+                    ptr,
+                    context: Context::new(),
+                })
+            }
+        }
+    }
+
+    /// Splits `data` into packets, without timestamps.
+    pub fn parse<'a>(&'a mut self, data: &'a [u8]) -> Parse<'a> {
+        self.parse_with(data, None, None, -1)
+    }
+
+    /// Splits `data` into packets. The timestamps and byte position describe
+    /// the start of `data`; the parser carries them over to whichever output
+    /// packet that byte ends up in.
+    pub fn parse_with<'a>(
+        &'a mut self,
+        data: &'a [u8],
+        pts: Option<i64>,
+        dts: Option<i64>,
+        position: isize,
+    ) -> Parse<'a> {
+        Parse {
+            parser: self,
+            data,
+            pts: pts.unwrap_or(AV_NOPTS_VALUE),
+            dts: dts.unwrap_or(AV_NOPTS_VALUE),
+            position: position as i64,
+            flush: false,
+        }
+    }
+
+    /// Signals end of stream and yields any frames still buffered.
+    pub fn flush(&mut self) -> Parse<'_> {
+        Parse {
+            parser: self,
+            data: &[],
+            pts: AV_NOPTS_VALUE,
+            dts: AV_NOPTS_VALUE,
+            position: -1,
+            flush: true,
+        }
+    }
+
+    fn parse_once(
+        &mut self,
+        data: &[u8],
+        pts: i64,
+        dts: i64,
+        position: i64,
+    ) -> Result<(usize, Option<Packet>), Error> {
+        unsafe {
+            let mut out = ptr::null_mut();
+            let mut size = 0;
+
+            let used = av_parser_parse2(
+                self.ptr,
+                self.context.as_mut_ptr(),
+                &mut out,
+                &mut size,
+                if data.is_empty() {
+                    ptr::null()
+                } else {
+                    data.as_ptr()
+                },
+                data.len() as c_int,
+                pts,
+                dts,
+                position,
+            );
+
+            if used < 0 {
+                return Err(Error::from(used));
+            }
+
+            if size == 0 {
+                return Ok((used as usize, None));
+            }
+
+            // The output may point into the parser's own buffer, which is
+            // overwritten by the next call, so it has to be copied out.
+            let mut packet = Packet::copy(std::slice::from_raw_parts(out, size as usize));
+
+            packet.set_pts(self.pts());
+            packet.set_dts(self.dts());
+            packet.set_position((*self.ptr).pos as isize);
+
+            if (*self.ptr).duration > 0 {
+                packet.set_duration((*self.ptr).duration as i64);
+            }
+
+            if self.is_key() {
+                packet.set_flags(Flags::KEY);
+            }
+
+            Ok((used as usize, Some(packet)))
+        }
+    }
+
+    /// Presentation timestamp of the last returned packet.
+    pub fn pts(&self) -> Option<i64> {
+        unsafe {
+            match (*self.as_ptr()).pts {
+                AV_NOPTS_VALUE => None,
+                pts => Some(pts),
+            }
+        }
+    }
+
+    /// Decoding timestamp of the last returned packet.
+    pub fn dts(&self) -> Option<i64> {
+        unsafe {
+            match (*self.as_ptr()).dts {
+                AV_NOPTS_VALUE => None,
+                dts => Some(dts),
+            }
+        }
+    }
+
+    pub fn is_key(&self) -> bool {
+        unsafe { (*self.as_ptr()).key_frame == 1 }
+    }
+
+    pub fn kind(&self) -> picture::Type {
+        unsafe {
+            picture::Type::from(mem::transmute::<c_int, AVPictureType>(
+                (*self.as_ptr()).pict_type,
+            ))
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).width as u32 }
+    }
+
+    pub fn height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).height as u32 }
+    }
+
+    pub fn coded_width(&self) -> u32 {
+        unsafe { (*self.as_ptr()).coded_width as u32 }
+    }
+
+    pub fn coded_height(&self) -> u32 {
+        unsafe { (*self.as_ptr()).coded_height as u32 }
+    }
+
+    /// Pixel format of the last returned video packet, if the parser knows it.
+    pub fn pixel_format(&self) -> format::Pixel {
+        unsafe {
+            match (*self.as_ptr()).format {
+                -1 => format::Pixel::None,
+                format => format::Pixel::from(mem::transmute::<c_int, AVPixelFormat>(format)),
+            }
+        }
+    }
+
+    /// Sample format of the last returned audio packet, if the parser knows
+    /// it.
+    pub fn sample_format(&self) -> format::Sample {
+        unsafe {
+            match (*self.as_ptr()).format {
+                -1 => format::Sample::None,
+                format => format::Sample::from(mem::transmute::<c_int, AVSampleFormat>(format)),
+            }
+        }
+    }
+
+    pub fn field_order(&self) -> FieldOrder {
+        unsafe { FieldOrder::from((*self.as_ptr()).field_order) }
+    }
+
+    pub fn repeat_picture(&self) -> i32 {
+        unsafe { (*self.as_ptr()).repeat_pict }
+    }
+}
+
+impl Drop for Parser {
+    fn drop(&mut self) {
+        unsafe {
+            av_parser_close(self.ptr);
+        }
+    }
+}
+
+/// Packets produced from one chunk of input, see [`Parser::parse`].
+pub struct Parse<'a> {
+    parser: &'a mut Parser,
+    data: &'a [u8],
+    pts: i64,
+    dts: i64,
+    position: i64,
+    flush: bool,
+}
+
+impl Iterator for Parse<'_> {
+    type Item = Result<Packet, Error>;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        loop {
+            // An empty buffer means end of stream to the parser, so only pass
+            // one when flushing was asked for.
+            if self.data.is_empty() && !self.flush {
+                return None;
+            }
+
+            let (used, packet) =
+                match self
+                    .parser
+                    .parse_once(self.data, self.pts, self.dts, self.position)
+                {
+                    Ok(result) => result,
+                    Err(error) => {
+                        self.data = &[];
+                        self.flush = false;
+
+                        return Some(Err(error));
+                    }
+                };
+
+            if used > 0 {
+                self.data = &self.data[used.min(self.data.len())..];
+
+                // The timestamps only describe the start of the chunk.
+                self.pts = AV_NOPTS_VALUE;
+                self.dts = AV_NOPTS_VALUE;
+                self.position = -1;
+            }
+
+            match packet {
+                Some(packet) => return Some(Ok(packet)),
+                None if self.flush => {
+                    self.flush = false;
+
+                    return None;
+                }
+                None if used == 0 => return None,
+                None => (),
+            }
+        }
+    }
+}
@@ -69,3 +69,5 @@ unsafe impl option::Target for Context {
 }
 
 impl option::Settable for Context {}
+impl option::Gettable for Context {}
+impl option::Iterable for Context {}
@@ -4,9 +4,10 @@ use std::ptr;
 use std::sync::Arc;
 
 use super::destructor::{self, Destructor};
+use super::summary::Summary;
 use crate::ffi::*;
-use crate::{Chapter, ChapterMut, DictionaryRef, Stream, StreamMut, media};
-use libc::{c_int, c_uint};
+use crate::{Chapter, ChapterMut, DictionaryRef, Stream, StreamMut, media, option};
+use libc::{c_int, c_uint, c_void};
 
 pub struct Context {
     ptr: *mut AVFormatContext,
@@ -143,6 +144,13 @@ impl Context {
     pub fn metadata(&self) -> DictionaryRef<'_> {
         unsafe { DictionaryRef::wrap((*self.as_ptr()).metadata) }
     }
+
+    /// Describes the container and its streams like `dump` does for the
+    /// file numbered `index`, without going through the FFmpeg log. Format
+    /// the result with `Display` to get the same text.
+    pub fn summary(&self, index: usize) -> Summary {
+        Summary::new(self, index)
+    }
 }
 
 pub struct Best<'a> {
@@ -400,6 +408,20 @@ impl<'a> Iterator for ChapterIterMut<'a> {
 
 impl<'a> ExactSizeIterator for ChapterIterMut<'a> {}
 
+unsafe impl option::Target for Context {
+    fn as_ptr(&self) -> *const c_void {
+        self.ptr as *const _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        self.ptr as *mut _
+    }
+}
+
+impl option::Settable for Context {}
+impl option::Gettable for Context {}
+impl option::Iterable for Context {}
+
 impl fmt::Debug for Context {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut s = fmt.debug_struct("AVFormatContext");
@@ -1,4 +1,4 @@
-use super::StreamIo;
+use super::{StreamIo, io};
 use crate::ffi::*;
 use crate::util::interrupt::InterruptGuard;
 
@@ -8,6 +8,7 @@ pub enum Mode {
     Output,
     InputCustomIo(StreamIo),
     OutputCustomIo(StreamIo),
+    OutputWithIo(io::Guard),
 }
 
 pub struct Destructor {
@@ -69,6 +70,13 @@ impl Drop for Destructor {
                     // flushes buffered data to the stream before freeing the
                     // AVIOContext.
                 }
+                Mode::OutputWithIo(_) => {
+                    // The main file, if the muxer has one, was opened through
+                    // the hooks too and is handed back through them; the
+                    // guard in `mode` drops the hooks afterwards.
+                    io::close(self.ptr);
+                    avformat_free_context(self.ptr);
+                }
                 Mode::Input => avformat_close_input(&mut self.ptr),
 
                 Mode::Output => {
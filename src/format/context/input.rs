@@ -1,14 +1,27 @@
 use std::ffi::CString;
 use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
 use super::common::Context;
 use super::destructor;
 #[cfg(not(feature = "ffmpeg_5_0"))]
 use crate::Codec;
 use crate::ffi::*;
+use crate::format::stream;
 use crate::util::range::Range;
-use crate::{Error, Packet, Stream, format};
+use crate::{Error, Frame, Packet, Rational, Rescale, Stream, decoder, format};
+use libc::c_int;
+
+/// How [`Input::seek_frame`] picks the frame it returns.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SeekMode {
+    /// The keyframe at or before the target, which is fast but may be well
+    /// before it.
+    Keyframe,
+    /// The first frame at or after the target.
+    Exact,
+}
 
 pub struct Input {
     ptr: *mut AVFormatContext,
@@ -164,20 +177,142 @@ impl Input {
     }
 
     pub fn seek<R: Range<i64>>(&mut self, ts: i64, range: R) -> Result<(), Error> {
+        self.seek_file(
+            -1,
+            range.start().cloned().unwrap_or(i64::MIN),
+            ts,
+            range.end().cloned().unwrap_or(i64::MAX),
+        )
+    }
+
+    /// Seeks `stream` to exactly `timestamp`, in the stream's time base, and
+    /// decodes the frame found there into `frame`.
+    ///
+    /// The demuxer is moved to the last keyframe at or before `timestamp`,
+    /// `decoder` (opened for `stream`) is flushed, and packets of the stream
+    /// are decoded from there. With [`SeekMode::Keyframe`] the first decoded
+    /// frame is returned; with [`SeekMode::Exact`] frames are discarded until
+    /// one's best-effort timestamp is at or after `timestamp`, or for audio
+    /// until one's samples reach past it, which takes care of reordered
+    /// B-frames and of audio priming samples. Frames without a timestamp
+    /// cannot be placed and are returned as they come. Packets of other
+    /// streams are dropped along the way.
+    ///
+    /// Fails with `StreamNotFound` for a bad index and with `Eof` if the stream
+    /// ends before such a frame.
+    pub fn seek_frame(
+        &mut self,
+        stream: usize,
+        timestamp: i64,
+        mode: SeekMode,
+        decoder: &mut decoder::Opened,
+        frame: &mut Frame,
+    ) -> Result<(), Error> {
+        let time_base = match self.stream(stream) {
+            Some(stream) => stream.time_base(),
+            None => return Err(Error::StreamNotFound),
+        };
+
+        // A target before the first keyframe has no keyframe at or before it;
+        // start from the first one after it instead.
+        if self
+            .seek_file(stream as c_int, i64::MIN, timestamp, timestamp)
+            .is_err()
+        {
+            self.seek_file(stream as c_int, i64::MIN, timestamp, i64::MAX)?;
+        }
+        decoder.flush();
+
+        let mut packet = Packet::empty();
+        let mut eof = false;
+
+        loop {
+            loop {
+                match decoder.receive_frame(frame) {
+                    Ok(()) => {
+                        if mode == SeekMode::Keyframe || reaches(frame, timestamp, time_base) {
+                            return Ok(());
+                        }
+                    }
+                    Err(Error::Other { errno }) if errno == libc::EAGAIN => break,
+                    Err(e) => return Err(e),
+                }
+            }
+
+            if eof {
+                return Err(Error::Eof);
+            }
+
+            match packet.read(self) {
+                Ok(()) if packet.stream() == stream => decoder.send_packet(&packet)?,
+                Ok(()) => (),
+                Err(Error::Eof) => {
+                    decoder.send_eof()?;
+                    eof = true;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Like [`seek_frame`](Self::seek_frame), with the target given as the
+    /// time since the start of the stream.
+    pub fn seek_frame_at(
+        &mut self,
+        stream: usize,
+        position: Duration,
+        mode: SeekMode,
+        decoder: &mut decoder::Opened,
+        frame: &mut Frame,
+    ) -> Result<(), Error> {
+        let (time_base, start) = match self.stream(stream) {
+            Some(stream) => (stream.time_base(), stream.start_time()),
+            None => return Err(Error::StreamNotFound),
+        };
+
+        let offset = i64::try_from(position.as_micros())
+            .unwrap_or(i64::MAX)
+            .rescale(Rational(1, 1_000_000), time_base);
+        let start = if start == AV_NOPTS_VALUE { 0 } else { start };
+
+        self.seek_frame(stream, start.saturating_add(offset), mode, decoder, frame)
+    }
+
+    /// Builds a full index of `stream` by reading every packet from the
+    /// current position to the end of the file, for formats whose
+    /// [`Stream::index_entries`] are incomplete or empty (raw elementary
+    /// streams, MPEG-TS, ...). Every packet gets an entry; keep the ones with
+    /// [`is_key`](stream::index::Entry::is_key) for cut points.
+    ///
+    /// Leaves the input at the end of the file; seek before reading again.
+    pub fn scan_index(&mut self, stream: usize) -> Result<Vec<stream::index::Entry>, Error> {
+        if stream >= self.nb_streams() as usize {
+            return Err(Error::StreamNotFound);
+        }
+
+        let mut entries = Vec::new();
+        let mut packet = Packet::empty();
+
+        loop {
+            match packet.read(self) {
+                Ok(()) if packet.stream() == stream => {
+                    entries.push(stream::index::Entry::from_packet(&packet))
+                }
+                Ok(()) => (),
+                Err(Error::Eof) => return Ok(entries),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn seek_file(&mut self, stream: c_int, min: i64, ts: i64, max: i64) -> Result<(), Error> {
         unsafe {
             let pb = (*self.ptr).pb;
             // Clear the latch BEFORE seeking: the seek machinery itself gates
             // on `eof_reached`/`error`, so a "clear only on success" ordering
             // cannot work.
             let relatch = unlatch_exit(pb);
-            let ret = avformat_seek_file(
-                self.as_mut_ptr(),
-                -1,
-                range.start().cloned().unwrap_or(i64::MIN),
-                ts,
-                range.end().cloned().unwrap_or(i64::MAX),
-                0,
-            );
+            let ret = avformat_seek_file(self.as_mut_ptr(), stream, min, ts, max, 0);
             if ret < 0 && relatch {
                 // The seek failed after we cleared the latch — and after
                 // `avformat_seek_file` already flushed/reset demuxer state
@@ -321,3 +456,61 @@ pub fn dump(ctx: &Input, index: i32, url: Option<&str>) {
         );
     }
 }
+
+/// Whether `frame`, decoded from a stream in `time_base`, is where an exact
+/// seek to `timestamp` lands: it starts at or after it, or is audio whose
+/// samples reach past it.
+fn reaches(frame: &Frame, timestamp: i64, time_base: Rational) -> bool {
+    let Some(start) = frame.timestamp() else {
+        return true;
+    };
+
+    if start >= timestamp {
+        return true;
+    }
+
+    let (samples, rate) = unsafe { ((*frame.as_ptr()).nb_samples, (*frame.as_ptr()).sample_rate) };
+
+    samples > 0
+        && rate > 0
+        && start.saturating_add(i64::from(samples).rescale(Rational(1, rate), time_base))
+            > timestamp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(timestamp: Option<i64>, samples: i32, rate: i32) -> Frame {
+        unsafe {
+            let mut frame = Frame::empty();
+            (*frame.as_mut_ptr()).best_effort_timestamp = timestamp.unwrap_or(AV_NOPTS_VALUE);
+            (*frame.as_mut_ptr()).nb_samples = samples;
+            (*frame.as_mut_ptr()).sample_rate = rate;
+
+            frame
+        }
+    }
+
+    #[test]
+    fn frames_without_timestamps_are_taken() {
+        assert!(reaches(&frame(None, 0, 0), 100, Rational(1, 25)));
+        assert!(reaches(&frame(None, 1024, 48000), 100, Rational(1, 48000)));
+    }
+
+    #[test]
+    fn video_frames_before_the_target_are_skipped() {
+        assert!(!reaches(&frame(Some(99), 0, 0), 100, Rational(1, 25)));
+        assert!(reaches(&frame(Some(100), 0, 0), 100, Rational(1, 25)));
+    }
+
+    #[test]
+    fn audio_frames_containing_the_target_are_taken() {
+        let time_base = Rational(1, 1000);
+
+        // 1152 samples at 44.1 kHz last 26 ms.
+        assert!(reaches(&frame(Some(261), 1152, 44100), 270, time_base));
+        assert!(!reaches(&frame(Some(235), 1152, 44100), 270, time_base));
+        assert!(!reaches(&frame(Some(244), 1152, 44100), 270, time_base));
+    }
+}
@@ -0,0 +1,232 @@
+//! Custom `io_open`/`io_close` hooks for muxers that write several files, see
+//! [`format::output_with_io`](crate::format::output_with_io).
+
+use std::ffi::CStr;
+use std::io::{Seek, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+
+use super::StreamIo;
+use crate::Error;
+use crate::ffi::*;
+use libc::{c_char, c_int, c_void};
+
+/// A destination the muxer can write one file to.
+///
+/// Implemented for [`StreamIo`] (which must be a write context) and for any
+/// `Write + Seek` stream, which is wrapped with [`StreamIo::from_write_seek`]
+/// and handed back unwrapped once the muxer closes the file.
+pub trait Writer: Send + Sized + 'static {
+    fn into_stream_io(self) -> Result<StreamIo, Error>;
+    fn from_stream_io(io: StreamIo) -> Option<Self>;
+}
+
+impl Writer for StreamIo {
+    fn into_stream_io(self) -> Result<StreamIo, Error> {
+        Ok(self)
+    }
+
+    fn from_stream_io(io: StreamIo) -> Option<Self> {
+        Some(io)
+    }
+}
+
+impl<T: Write + Seek + Send + 'static> Writer for T {
+    fn into_stream_io(self) -> Result<StreamIo, Error> {
+        StreamIo::from_write_seek(self)
+    }
+
+    fn from_stream_io(io: StreamIo) -> Option<Self> {
+        io.into_inner::<T>().ok()
+    }
+}
+
+type Open<T> = dyn FnMut(&str) -> Result<T, Error> + Send;
+type Close<T> = dyn FnMut(&str, T) + Send;
+
+/// The boxed `AVFormatContext.opaque` behind the hooks: the user closures
+/// and every file opened through them that the muxer has not closed yet.
+struct Hooks<T> {
+    open: Box<Open<T>>,
+    close: Box<Close<T>>,
+    files: Vec<File>,
+}
+
+struct File {
+    pb: *mut AVIOContext,
+    url: String,
+    io: StreamIo,
+}
+
+/// Keep-alive for the hooks installed on an output context. Dropping it
+/// drops the closures and any file the muxer never closed (flushing it, but
+/// without a close notification), so it must outlive the context.
+pub struct Guard {
+    opaque: *mut c_void,
+    drop_fn: unsafe fn(*mut c_void),
+}
+
+// SAFETY: the closures and the wrapped streams are `Send`; the hooks are
+// only ever reached through the format context, from the thread driving it.
+unsafe impl Send for Guard {}
+unsafe impl Sync for Guard {}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        unsafe { (self.drop_fn)(self.opaque) };
+    }
+}
+
+impl std::fmt::Debug for Guard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Guard")
+            .field("opaque", &self.opaque)
+            .finish()
+    }
+}
+
+/// Installs `open` and `close` as the `io_open`/`io_close` callbacks of `ps`,
+/// using its `opaque` field. Nested muxers (HLS, DASH, segment) copy both to
+/// the contexts they create, so the hooks see their files too.
+pub(crate) unsafe fn install<T, O, C>(ps: *mut AVFormatContext, open: O, close: C) -> Guard
+where
+    T: Writer,
+    O: FnMut(&str) -> Result<T, Error> + Send + 'static,
+    C: FnMut(&str, T) + Send + 'static,
+{
+    let opaque = Box::into_raw(Box::new(Hooks {
+        open: Box::new(open) as Box<Open<T>>,
+        close: Box::new(close) as Box<Close<T>>,
+        files: Vec::new(),
+    })) as *mut c_void;
+
+    unsafe {
+        (*ps).opaque = opaque;
+        (*ps).io_open = Some(io_open::<T>);
+
+        #[cfg(feature = "ffmpeg_5_0")]
+        {
+            (*ps).io_close2 = Some(io_close::<T>);
+        }
+        #[cfg(not(feature = "ffmpeg_5_0"))]
+        {
+            (*ps).io_close = Some(io_close_void::<T>);
+        }
+    }
+
+    Guard {
+        opaque,
+        drop_fn: drop_box::<Hooks<T>>,
+    }
+}
+
+/// Opens the main file of a muxer that has one through the installed hooks.
+pub(crate) unsafe fn open(ps: *mut AVFormatContext, url: *const c_char) -> c_int {
+    unsafe {
+        match (*ps).io_open {
+            Some(open) => open(
+                ps,
+                &mut (*ps).pb,
+                url,
+                AVIO_FLAG_WRITE,
+                std::ptr::null_mut(),
+            ),
+            None => AVERROR(EINVAL),
+        }
+    }
+}
+
+/// Closes the main file, if any, through the installed hooks.
+pub(crate) unsafe fn close(ps: *mut AVFormatContext) {
+    unsafe {
+        let pb = (*ps).pb;
+
+        if pb.is_null() {
+            return;
+        }
+
+        (*ps).pb = std::ptr::null_mut();
+
+        #[cfg(feature = "ffmpeg_5_0")]
+        if let Some(close) = (*ps).io_close2 {
+            close(ps, pb);
+        }
+        #[cfg(not(feature = "ffmpeg_5_0"))]
+        if let Some(close) = (*ps).io_close {
+            close(ps, pb);
+        }
+    }
+}
+
+unsafe extern "C" fn io_open<T: Writer>(
+    s: *mut AVFormatContext,
+    pb: *mut *mut AVIOContext,
+    url: *const c_char,
+    flags: c_int,
+    _options: *mut *mut AVDictionary,
+) -> c_int {
+    unsafe {
+        // Only writing is supported; muxers that read their own output back
+        // (HLS `append_list`, MP4 `faststart`) fail here.
+        if flags & AVIO_FLAG_READ != 0 {
+            return AVERROR(ENOSYS);
+        }
+
+        let hooks = &mut *((*s).opaque as *mut Hooks<T>);
+        let url = CStr::from_ptr(url).to_string_lossy().into_owned();
+
+        let result = match panic::catch_unwind(AssertUnwindSafe(|| (hooks.open)(&url))) {
+            Ok(result) => result.and_then(Writer::into_stream_io),
+            Err(_) => process::abort(),
+        };
+
+        let mut io = match result {
+            Ok(io) if io.is_writable() => io,
+            Ok(_) => return AVERROR(EINVAL),
+            Err(e) => return e.into(),
+        };
+
+        io.set_interrupt((*s).interrupt_callback);
+        *pb = io.as_mut_ptr();
+        hooks.files.push(File { pb: *pb, url, io });
+
+        0
+    }
+}
+
+unsafe extern "C" fn io_close<T: Writer>(s: *mut AVFormatContext, pb: *mut AVIOContext) -> c_int {
+    unsafe {
+        let hooks = &mut *((*s).opaque as *mut Hooks<T>);
+
+        let File { url, io, .. } = match hooks.files.iter().position(|file| file.pb == pb) {
+            Some(index) => hooks.files.remove(index),
+            // Not one of ours, do what the default callback would.
+            None => return avio_close(pb),
+        };
+
+        avio_flush(pb);
+        let error = (*pb).error;
+
+        let stream = T::from_stream_io(io);
+        let close = AssertUnwindSafe(|| stream.map(|stream| (hooks.close)(&url, stream)));
+
+        if panic::catch_unwind(close).is_err() {
+            process::abort();
+        }
+
+        if error < 0 { error } else { 0 }
+    }
+}
+
+#[cfg(not(feature = "ffmpeg_5_0"))]
+unsafe extern "C" fn io_close_void<T: Writer>(s: *mut AVFormatContext, pb: *mut AVIOContext) {
+    unsafe {
+        io_close::<T>(s, pb);
+    }
+}
+
+unsafe fn drop_box<T>(opaque: *mut c_void) {
+    unsafe {
+        drop(Box::from_raw(opaque as *mut T));
+    }
+}
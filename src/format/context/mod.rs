@@ -10,6 +10,11 @@ pub use self::output::Output;
 pub mod stream_io;
 pub use self::stream_io::StreamIo;
 
+pub mod io;
+
+pub mod summary;
+pub use self::summary::Summary;
+
 #[doc(hidden)]
 pub mod common;
 
@@ -3,7 +3,7 @@ use std::mem::size_of;
 use std::ops::{Deref, DerefMut};
 use std::ptr;
 
-use libc;
+use libc::{self, c_int};
 
 use super::common::Context;
 use super::destructor;
@@ -18,6 +18,31 @@ pub enum AvStreamInitStatus {
     InInitOutput,
 }
 
+/// How the muxer shifts timestamps to keep them from going negative, as the
+/// `avoid_negative_ts` option.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AvoidNegativeTs {
+    /// Shift only if the format cannot store negative timestamps.
+    Auto,
+    /// Never shift.
+    Disabled,
+    /// Shift so that no timestamp is negative.
+    MakeNonNegative,
+    /// Shift so that the first timestamp is zero.
+    MakeZero,
+}
+
+impl From<AvoidNegativeTs> for c_int {
+    fn from(value: AvoidNegativeTs) -> c_int {
+        match value {
+            AvoidNegativeTs::Auto => AVFMT_AVOID_NEG_TS_AUTO,
+            AvoidNegativeTs::Disabled => 0,
+            AvoidNegativeTs::MakeNonNegative => AVFMT_AVOID_NEG_TS_MAKE_NON_NEGATIVE,
+            AvoidNegativeTs::MakeZero => AVFMT_AVOID_NEG_TS_MAKE_ZERO,
+        }
+    }
+}
+
 pub struct Output {
     ptr: *mut AVFormatContext,
     ctx: Context,
@@ -46,6 +71,15 @@ impl Output {
         }
     }
 
+    pub unsafe fn wrap_with_io(ptr: *mut AVFormatContext, guard: super::io::Guard) -> Self {
+        unsafe {
+            Output {
+                ptr,
+                ctx: Context::wrap(ptr, destructor::Mode::OutputWithIo(guard)),
+            }
+        }
+    }
+
     pub unsafe fn as_ptr(&self) -> *const AVFormatContext {
         self.ptr as *const _
     }
@@ -201,6 +235,13 @@ impl Output {
             (*self.as_mut_ptr()).metadata = dictionary.disown();
         }
     }
+
+    /// Must be called before [`write_header`](Self::write_header).
+    pub fn set_avoid_negative_ts(&mut self, value: AvoidNegativeTs) {
+        unsafe {
+            (*self.as_mut_ptr()).avoid_negative_ts = value.into();
+        }
+    }
 }
 
 impl Deref for Output {
@@ -0,0 +1,309 @@
+use std::ffi::CStr;
+use std::fmt;
+
+use super::common::Context;
+use crate::codec::packet::side_data;
+use crate::ffi::*;
+use crate::format::stream::Disposition;
+use crate::{DictionaryRef, Rational, codec, media};
+use libc::{c_char, c_int};
+
+/// What `av_dump_format` prints about a format context, as a value.
+///
+/// Obtained from [`Context::summary`](super::common::Context::summary). The
+/// [`Display`](fmt::Display) implementation renders it in the familiar
+/// `ffmpeg -i` layout, so `summary.to_string()` replaces `dump` wherever the
+/// text should not end up in the FFmpeg log.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    /// The number of the file, as given to `av_dump_format`, which prefixes
+    /// chapter and stream numbers.
+    pub index: usize,
+    pub output: bool,
+    /// Short name of the demuxer or muxer.
+    pub format: String,
+    pub url: String,
+    /// In `AV_TIME_BASE` units.
+    pub duration: Option<i64>,
+    /// In `AV_TIME_BASE` units.
+    pub start_time: Option<i64>,
+    /// In bits per second.
+    pub bit_rate: Option<i64>,
+    pub metadata: Vec<(String, String)>,
+    pub chapters: Vec<ChapterSummary>,
+    pub streams: Vec<StreamSummary>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ChapterSummary {
+    pub id: i64,
+    pub time_base: Rational,
+    pub start: i64,
+    pub end: i64,
+    pub metadata: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StreamSummary {
+    pub index: usize,
+    pub id: i32,
+    pub medium: media::Type,
+    pub codec: codec::Id,
+    /// The codec line as printed by FFmpeg, e.g.
+    /// `"Video: h264 (High) (avc1 / 0x31637661), yuv420p, 1920x1080"`.
+    pub description: String,
+    /// In bits per second.
+    pub bit_rate: Option<i64>,
+    pub time_base: Rational,
+    pub rate: Rational,
+    pub avg_frame_rate: Rational,
+    pub disposition: Disposition,
+    pub metadata: Vec<(String, String)>,
+    pub side_data: Vec<side_data::Type>,
+}
+
+impl StreamSummary {
+    pub fn language(&self) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(key, _)| key == "language")
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Summary {
+    pub(crate) fn new(context: &Context, index: usize) -> Self {
+        unsafe {
+            let ptr = context.as_ptr();
+            let output = (*ptr).iformat.is_null();
+
+            let format = if output {
+                string((*(*ptr).oformat).name)
+            } else {
+                string((*(*ptr).iformat).name)
+            };
+
+            #[cfg(feature = "ffmpeg_4_1")]
+            let url = string((*ptr).url);
+            #[cfg(not(feature = "ffmpeg_4_1"))]
+            let url = string((*ptr).filename.as_ptr());
+
+            Summary {
+                index,
+                output,
+                format,
+                url,
+                duration: timestamp((*ptr).duration),
+                start_time: timestamp((*ptr).start_time),
+                bit_rate: rate((*ptr).bit_rate),
+                metadata: entries(context.metadata()),
+                chapters: context
+                    .chapters()
+                    .map(|chapter| ChapterSummary {
+                        id: chapter.id(),
+                        time_base: chapter.time_base(),
+                        start: chapter.start(),
+                        end: chapter.end(),
+                        metadata: entries(chapter.metadata()),
+                    })
+                    .collect(),
+                streams: context
+                    .streams()
+                    .map(|stream| {
+                        let parameters = stream.parameters();
+
+                        StreamSummary {
+                            index: stream.index(),
+                            id: stream.id(),
+                            medium: parameters.medium(),
+                            codec: parameters.id(),
+                            description: describe(&parameters, output),
+                            bit_rate: rate((*parameters.as_ptr()).bit_rate),
+                            time_base: stream.time_base(),
+                            rate: stream.rate(),
+                            avg_frame_rate: stream.avg_frame_rate(),
+                            disposition: stream.disposition(),
+                            metadata: entries(stream.metadata()),
+                            side_data: stream.side_data().map(|data| data.kind()).collect(),
+                        }
+                    })
+                    .collect(),
+            }
+        }
+    }
+}
+
+unsafe fn string(ptr: *const c_char) -> String {
+    unsafe {
+        if ptr.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(ptr).to_string_lossy().into_owned()
+        }
+    }
+}
+
+fn timestamp(value: i64) -> Option<i64> {
+    match value {
+        AV_NOPTS_VALUE => None,
+        value => Some(value),
+    }
+}
+
+fn rate(value: i64) -> Option<i64> {
+    if value > 0 { Some(value) } else { None }
+}
+
+fn entries(dictionary: DictionaryRef) -> Vec<(String, String)> {
+    dictionary
+        .iter()
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+/// Runs `avcodec_string` on a codec context filled from `parameters`, the way
+/// `av_dump_format` does.
+fn describe(parameters: &codec::Parameters, encode: bool) -> String {
+    let context = match codec::Context::from_parameters(parameters.clone()) {
+        Ok(context) => context,
+        Err(_) => return format!("{:?}", parameters.id()),
+    };
+
+    let mut buffer = [0 as c_char; 256];
+
+    unsafe {
+        avcodec_string(
+            buffer.as_mut_ptr(),
+            buffer.len() as c_int,
+            context.as_ptr() as *mut _,
+            encode as c_int,
+        );
+
+        string(buffer.as_ptr())
+    }
+}
+
+fn write_time(f: &mut fmt::Formatter, value: i64) -> fmt::Result {
+    let centis = value.saturating_add(5000) / 10000;
+    let seconds = centis / 100;
+
+    write!(
+        f,
+        "{:02}:{:02}:{:02}.{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        centis % 100
+    )
+}
+
+fn write_metadata(
+    f: &mut fmt::Formatter,
+    indent: &str,
+    metadata: &[(String, String)],
+) -> fmt::Result {
+    if metadata.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(f, "{indent}Metadata:")?;
+
+    for (key, value) in metadata {
+        writeln!(f, "{indent}  {key:<16}: {}", value.replace('\n', " "))?;
+    }
+
+    Ok(())
+}
+
+fn seconds(value: i64, time_base: Rational) -> f64 {
+    value as f64 * f64::from(time_base)
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.output {
+            writeln!(
+                f,
+                "Output #{}, {}, to '{}':",
+                self.index, self.format, self.url
+            )?;
+        } else {
+            writeln!(
+                f,
+                "Input #{}, {}, from '{}':",
+                self.index, self.format, self.url
+            )?;
+        }
+
+        write_metadata(f, "  ", &self.metadata)?;
+
+        if !self.output {
+            write!(f, "  Duration: ")?;
+            match self.duration {
+                Some(duration) => write_time(f, duration)?,
+                None => write!(f, "N/A")?,
+            }
+            if let Some(start) = self.start_time {
+                write!(f, ", start: {:.6}", start as f64 / AV_TIME_BASE as f64)?;
+            }
+            match self.bit_rate {
+                Some(bit_rate) => writeln!(f, ", bitrate: {} kb/s", bit_rate / 1000)?,
+                None => writeln!(f, ", bitrate: N/A")?,
+            }
+        }
+
+        for (index, chapter) in self.chapters.iter().enumerate() {
+            writeln!(
+                f,
+                "    Chapter #{}:{}: start {:.6}, end {:.6}",
+                self.index,
+                index,
+                seconds(chapter.start, chapter.time_base),
+                seconds(chapter.end, chapter.time_base)
+            )?;
+            write_metadata(f, "      ", &chapter.metadata)?;
+        }
+
+        for stream in &self.streams {
+            write!(f, "  Stream #{}:{}", self.index, stream.index)?;
+            if stream.id != 0 {
+                write!(f, "[0x{:x}]", stream.id)?;
+            }
+            if let Some(language) = stream.language() {
+                write!(f, "({language})")?;
+            }
+            write!(f, ": {}", stream.description)?;
+
+            if stream.medium == media::Type::Video {
+                let avg_frame_rate = f64::from(stream.avg_frame_rate);
+                let rate = f64::from(stream.rate);
+
+                if stream.avg_frame_rate.denominator() != 0 && avg_frame_rate > 0.0 {
+                    write!(f, ", {avg_frame_rate:.2} fps")?;
+                }
+                if stream.rate.denominator() != 0 && rate > 0.0 {
+                    write!(f, ", {rate:.2} tbr")?;
+                }
+                if stream.time_base.numerator() != 0 {
+                    write!(f, ", {:.0} tbn", 1.0 / f64::from(stream.time_base))?;
+                }
+            }
+
+            for (name, _) in stream.disposition.iter_names() {
+                write!(f, " ({})", name.to_lowercase().replace('_', " "))?;
+            }
+            writeln!(f)?;
+
+            write_metadata(f, "    ", &stream.metadata)?;
+
+            if !stream.side_data.is_empty() {
+                writeln!(f, "    Side data:")?;
+                for kind in &stream.side_data {
+                    writeln!(f, "      {kind:?}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
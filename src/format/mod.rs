@@ -17,6 +17,11 @@ pub use self::format::{Input, Output};
 
 pub mod network;
 
+pub mod probe;
+pub use self::probe::{probe, probe_stream};
+
+pub mod remux;
+
 use std::ffi::{CStr, CString};
 use std::path::Path;
 use std::ptr;
@@ -57,6 +62,21 @@ pub fn license() -> &'static str {
     unsafe { from_utf8_unchecked(CStr::from_ptr(avformat_license()).to_bytes()) }
 }
 
+/// Looks up a demuxer by short name, e.g. `"mov"` or `"mpegts"`.
+pub fn find_input(name: &str) -> Option<Input> {
+    let name = CString::new(name).ok()?;
+
+    unsafe {
+        let ptr = av_find_input_format(name.as_ptr());
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Input::wrap(ptr as *mut _))
+        }
+    }
+}
+
 // XXX: use to_cstring when stable
 fn from_path<P: AsRef<Path> + ?Sized>(path: &P) -> CString {
     CString::new(path.as_ref().as_os_str().to_str().unwrap()).unwrap()
@@ -199,6 +219,42 @@ pub fn input_with_dictionary<P: AsRef<Path> + ?Sized>(
     }
 }
 
+/// Opens an input with the demuxer named `format` instead of probing for
+/// one, e.g. `"h264"` for a raw elementary stream without an extension.
+/// Fails with `DemuxerNotFound` if there is no such demuxer.
+pub fn input_as<P: AsRef<Path> + ?Sized>(path: &P, format: &str) -> Result<context::Input, Error> {
+    input_as_with(path, format, Dictionary::new())
+}
+
+pub fn input_as_with<P: AsRef<Path> + ?Sized>(
+    path: &P,
+    format: &str,
+    options: Dictionary,
+) -> Result<context::Input, Error> {
+    let format = find_input(format).ok_or(Error::DemuxerNotFound)?;
+
+    unsafe {
+        let mut ps = ptr::null_mut();
+        let path = from_path(path);
+        let mut opts = options.disown();
+        let res = avformat_open_input(&mut ps, path.as_ptr(), format.as_ptr() as *mut _, &mut opts);
+
+        Dictionary::own(opts);
+
+        match res {
+            0 => match avformat_find_stream_info(ps, ptr::null_mut()) {
+                r if r >= 0 => Ok(context::Input::wrap(ps)),
+                e => {
+                    avformat_close_input(&mut ps);
+                    Err(Error::from(e))
+                }
+            },
+
+            e => Err(Error::from(e)),
+        }
+    }
+}
+
 pub fn input_with_interrupt<P: AsRef<Path> + ?Sized, F>(
     path: &P,
     closure: F,
@@ -274,7 +330,20 @@ pub fn input_from_stream(
     filename: Option<&str>,
     options: Option<Dictionary>,
 ) -> Result<context::Input, Error> {
-    input_from_stream_impl(custom_io, filename, options, None)
+    input_from_stream_impl(custom_io, filename, None, options, None)
+}
+
+/// Like [`input_from_stream`], with the demuxer named `format` instead of
+/// probing for one, e.g. the result of [`probe_stream`]. Fails with
+/// `DemuxerNotFound` if there is no such demuxer.
+pub fn input_from_stream_as(
+    custom_io: context::StreamIo,
+    format: &str,
+    options: Option<Dictionary>,
+) -> Result<context::Input, Error> {
+    let format = find_input(format).ok_or(Error::DemuxerNotFound)?;
+
+    input_from_stream_impl(custom_io, None, Some(format), options, None)
 }
 
 /// Like [`input_from_stream`], with an interrupt callback FFmpeg polls to
@@ -297,6 +366,7 @@ where
     input_from_stream_impl(
         custom_io,
         filename,
+        None,
         options,
         Some(interrupt::new(Box::new(closure))),
     )
@@ -316,6 +386,7 @@ where
 fn input_from_stream_impl(
     mut custom_io: context::StreamIo,
     filename: Option<&str>,
+    format: Option<Input>,
     options: Option<Dictionary>,
     interrupt: Option<interrupt::Interrupt>,
 ) -> Result<context::Input, Error> {
@@ -325,6 +396,9 @@ fn input_from_stream_impl(
 
     let filename = opt_cstring(filename)?;
     let filename_ptr = filename.as_ref().map_or(ptr::null(), |f| f.as_ptr());
+    let format_ptr = format
+        .as_ref()
+        .map_or(ptr::null(), |f| unsafe { f.as_ptr() });
 
     unsafe {
         let mut ps = avformat_alloc_context();
@@ -340,11 +414,11 @@ fn input_from_stream_impl(
 
         let result = if let Some(opts) = options {
             let mut opts = opts.disown();
-            let res = avformat_open_input(&mut ps, filename_ptr, ptr::null_mut(), &mut opts);
+            let res = avformat_open_input(&mut ps, filename_ptr, format_ptr as *mut _, &mut opts);
             Dictionary::own(opts);
             res
         } else {
-            avformat_open_input(&mut ps, filename_ptr, ptr::null_mut(), ptr::null_mut())
+            avformat_open_input(&mut ps, filename_ptr, format_ptr as *mut _, ptr::null_mut())
         };
 
         match result {
@@ -529,6 +603,65 @@ pub fn output_to_stream(
     }
 }
 
+/// Creates an output context that opens every file through `open` instead of
+/// the filesystem, for muxers that write more than one file (`hls`, `dash`,
+/// `segment`, `image2`, ...) as well as ordinary single-file ones.
+///
+/// `open` is called with the URL of each file the muxer (or a muxer nested
+/// in it) opens, starting with `filename` itself unless the muxer is
+/// `AVFMT_NOFILE`, and returns either a writable `context::StreamIo` or any
+/// `Write + Seek` stream. When the muxer is done with a file, its buffered
+/// data is flushed and the stream is handed back to `close` along with the
+/// URL. The main file is only closed when the returned context is dropped.
+///
+/// Muxers that read their own output back (HLS with `append_list`, MP4 with
+/// `faststart`) are not supported and fail with `ENOSYS`. The hooks use
+/// `AVFormatContext.opaque`, which must be left alone. Fails with `EINVAL`
+/// if `filename` / `format` contain an interior NUL byte.
+pub fn output_with_io<T, O, C>(
+    filename: &str,
+    format: Option<&str>,
+    open: O,
+    close: C,
+) -> Result<context::Output, Error>
+where
+    T: context::io::Writer,
+    O: FnMut(&str) -> Result<T, Error> + Send + 'static,
+    C: FnMut(&str, T) + Send + 'static,
+{
+    let filename = CString::new(filename).map_err(|_| Error::Other { errno: EINVAL })?;
+
+    let format = opt_cstring(format)?;
+    let format_ptr = format.as_ref().map_or(ptr::null(), |f| f.as_ptr());
+
+    unsafe {
+        let mut ps = ptr::null_mut();
+
+        match avformat_alloc_output_context2(
+            &mut ps,
+            ptr::null_mut(),
+            format_ptr,
+            filename.as_ptr(),
+        ) {
+            0 => {
+                let guard = context::io::install(ps, open, close);
+                let mut output = context::Output::wrap_with_io(ps, guard);
+
+                if (*(*ps).oformat).flags & AVFMT_NOFILE == 0 {
+                    match context::io::open(output.as_mut_ptr(), filename.as_ptr()) {
+                        0 => (),
+                        e => return Err(Error::from(e)),
+                    }
+                }
+
+                Ok(output)
+            }
+
+            e => Err(Error::from(e)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,80 @@
+use std::ffi::CString;
+use std::ptr;
+
+use super::context::StreamIo;
+use super::format::Input;
+use crate::Error;
+use crate::ffi::*;
+use libc::c_int;
+
+/// The most data libavformat itself ever probes (`PROBE_BUF_MAX`).
+const MAX_PROBE_SIZE: usize = 1 << 20;
+
+/// Guesses the container of `data`, the beginning of a file, without opening
+/// it. `filename` lets the extension count towards the guess.
+///
+/// Returns the most likely demuxer with its score, out of
+/// `AVPROBE_SCORE_MAX` (100); scores of 25 or less are little more than an
+/// extension match. Only the first MiB of `data` is examined.
+pub fn probe(data: &[u8], filename: Option<&str>) -> Option<(Input, i32)> {
+    let filename = CString::new(filename.unwrap_or("")).ok()?;
+    let data = &data[..data.len().min(MAX_PROBE_SIZE)];
+
+    // Demuxer probes may read past the end of the buffer, which must be
+    // followed by zeroed padding.
+    let mut buffer = Vec::with_capacity(data.len() + AVPROBE_PADDING_SIZE as usize);
+    buffer.extend_from_slice(data);
+    buffer.resize(data.len() + AVPROBE_PADDING_SIZE as usize, 0);
+
+    unsafe {
+        let mut probe_data: AVProbeData = std::mem::zeroed();
+        probe_data.filename = filename.as_ptr();
+        probe_data.buf = buffer.as_mut_ptr();
+        probe_data.buf_size = data.len() as c_int;
+
+        let mut score = 0;
+        let format = av_probe_input_format3(&mut probe_data, 1, &mut score);
+
+        if format.is_null() {
+            None
+        } else {
+            Some((Input::wrap(format as *mut _), score))
+        }
+    }
+}
+
+/// Guesses the container of a readable `StreamIo` by reading from it
+/// progressively, as `format::input_from_stream` would.
+///
+/// The data read is kept in the stream's buffer, so `io` can be handed to
+/// [`input_from_stream_as`](super::input_from_stream_as) afterwards without
+/// seeking, together with the detected format to skip probing a second time.
+/// Fails with `InvalidData` if nothing matches, and with `EINVAL` if `io` is
+/// a write context or `filename` contains an interior NUL byte.
+pub fn probe_stream(io: &mut StreamIo, filename: Option<&str>) -> Result<(Input, i32), Error> {
+    if io.is_writable() {
+        return Err(Error::Other { errno: EINVAL });
+    }
+
+    let filename =
+        CString::new(filename.unwrap_or("")).map_err(|_| Error::Other { errno: EINVAL })?;
+
+    unsafe {
+        #[cfg(feature = "ffmpeg_5_0")]
+        let mut format = ptr::null();
+        #[cfg(not(feature = "ffmpeg_5_0"))]
+        let mut format = ptr::null_mut();
+
+        match av_probe_input_buffer2(
+            io.as_mut_ptr(),
+            &mut format,
+            filename.as_ptr(),
+            ptr::null_mut(),
+            0,
+            0,
+        ) {
+            e if e < 0 => Err(Error::from(e)),
+            score => Ok((Input::wrap(format as *mut _), score)),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::format::context::{Input, Output};
+use crate::{Error, Packet, Rational, Rescale, codec, media, rescale};
+
+/// What differs between a stream and the matching stream of the first input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch {
+    Medium,
+    Codec,
+    Dimensions,
+    PixelFormat,
+    SampleFormat,
+    SampleRate,
+    Channels,
+    /// Out-of-band headers, such as H.264 parameter sets, which the output
+    /// carries only once.
+    Extradata,
+}
+
+#[derive(Debug)]
+pub enum ConcatError {
+    /// There are no inputs, or no streams to copy.
+    Empty,
+    /// Input `input` does not have as many streams to copy as the first one.
+    StreamCount {
+        input: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The `stream`th copied stream of input `input` cannot follow the first
+    /// input's.
+    Incompatible {
+        input: usize,
+        stream: usize,
+        mismatch: Mismatch,
+    },
+    Ffmpeg(Error),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Mismatch::Medium => "media type",
+            Mismatch::Codec => "codec",
+            Mismatch::Dimensions => "dimensions",
+            Mismatch::PixelFormat => "pixel format",
+            Mismatch::SampleFormat => "sample format",
+            Mismatch::SampleRate => "sample rate",
+            Mismatch::Channels => "channels",
+            Mismatch::Extradata => "extradata",
+        })
+    }
+}
+
+impl fmt::Display for ConcatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConcatError::Empty => write!(f, "nothing to concatenate"),
+            ConcatError::StreamCount {
+                input,
+                expected,
+                found,
+            } => write!(
+                f,
+                "input {input} has {found} streams to copy, expected {expected}"
+            ),
+            ConcatError::Incompatible {
+                input,
+                stream,
+                mismatch,
+            } => write!(
+                f,
+                "stream {stream} of input {input} differs from the first input in {mismatch}"
+            ),
+            ConcatError::Ffmpeg(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for ConcatError {
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            ConcatError::Ffmpeg(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ConcatError {
+    fn from(value: Error) -> ConcatError {
+        ConcatError::Ffmpeg(value)
+    }
+}
+
+/// Copies `inputs`, one after the other, into `output` without re-encoding,
+/// like the concat demuxer with `-c copy`, and returns where each input
+/// starts in the output.
+///
+/// Every input must have the same audio, video and subtitle streams, in the
+/// same order, with matching codec parameters; this is checked before
+/// anything is written, and `output` must not have streams yet. Timestamps
+/// are rescaled from each input's time bases to the output's and offset so
+/// that every input starts where the longest stream of the previous one
+/// ended.
+pub fn concat(inputs: &mut [Input], output: &mut Output) -> Result<Vec<Duration>, ConcatError> {
+    let first = inputs.first().ok_or(ConcatError::Empty)?;
+    let expected = parameters(first);
+
+    if expected.is_empty() {
+        return Err(ConcatError::Empty);
+    }
+
+    for (input, other) in inputs.iter().enumerate().skip(1) {
+        let found = parameters(other);
+
+        if found.len() != expected.len() {
+            return Err(ConcatError::StreamCount {
+                input,
+                expected: expected.len(),
+                found: found.len(),
+            });
+        }
+
+        for (stream, (expected, found)) in expected.iter().zip(&found).enumerate() {
+            if let Some(mismatch) = compare(expected, found) {
+                return Err(ConcatError::Incompatible {
+                    input,
+                    stream,
+                    mismatch,
+                });
+            }
+        }
+    }
+
+    let destinations: Vec<usize> = super::map_streams(first, output)?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    output.write_header()?;
+
+    let mut offsets = Vec::with_capacity(inputs.len());
+    let mut offset = 0;
+
+    for input in inputs.iter_mut() {
+        offsets.push(super::duration(offset));
+        offset += append(input, output, &destinations, offset)?;
+    }
+
+    output.write_trailer()?;
+
+    Ok(offsets)
+}
+
+fn parameters(input: &Input) -> Vec<codec::Parameters> {
+    input
+        .streams()
+        .filter(super::copied)
+        .map(|stream| stream.parameters())
+        .collect()
+}
+
+fn compare(expected: &codec::Parameters, found: &codec::Parameters) -> Option<Mismatch> {
+    if expected.medium() != found.medium() {
+        return Some(Mismatch::Medium);
+    }
+    if expected.id() != found.id() {
+        return Some(Mismatch::Codec);
+    }
+
+    match expected.medium() {
+        media::Type::Video => {
+            if (expected.width(), expected.height()) != (found.width(), found.height()) {
+                return Some(Mismatch::Dimensions);
+            }
+            if expected.pixel_format() != found.pixel_format() {
+                return Some(Mismatch::PixelFormat);
+            }
+        }
+
+        media::Type::Audio => {
+            if expected.sample_format() != found.sample_format() {
+                return Some(Mismatch::SampleFormat);
+            }
+            if expected.sample_rate() != found.sample_rate() {
+                return Some(Mismatch::SampleRate);
+            }
+            if expected.channels() != found.channels() {
+                return Some(Mismatch::Channels);
+            }
+        }
+
+        _ => (),
+    }
+
+    if expected.extradata() != found.extradata() {
+        return Some(Mismatch::Extradata);
+    }
+
+    None
+}
+
+/// Copies all of `input` to `offset` in the output and returns its length,
+/// in `AV_TIME_BASE` units.
+fn append(
+    input: &mut Input,
+    output: &mut Output,
+    destinations: &[usize],
+    offset: i64,
+) -> Result<i64, Error> {
+    let origin = super::start_time(input);
+    let time_bases: Vec<Rational> = input.streams().map(|stream| stream.time_base()).collect();
+    let mut targets = vec![None; time_bases.len()];
+
+    for (stream, &destination) in input.streams().filter(super::copied).zip(destinations) {
+        targets[stream.index()] = Some(destination);
+    }
+
+    let mut previous = vec![None; time_bases.len()];
+    let mut length = 0;
+
+    loop {
+        let mut packet = Packet::empty();
+
+        match packet.read(input) {
+            Ok(()) => (),
+            Err(Error::Eof) => return Ok(length),
+            Err(e) => return Err(e),
+        }
+
+        let index = packet.stream();
+        let destination = match targets.get(index) {
+            Some(&Some(destination)) => destination,
+            _ => continue,
+        };
+        let time_base = time_bases[index];
+
+        if let Some(ts) = packet.pts().or(packet.dts()) {
+            // Without a duration, assume the packet lasts as long as the
+            // previous one of its stream.
+            let duration = match (packet.duration(), previous[index]) {
+                (0, Some(previous)) => ts - previous,
+                (duration, _) => duration,
+            };
+            let end = (ts + duration).rescale(time_base, rescale::TIME_BASE);
+            length = length.max(end - origin);
+            previous[index] = Some(ts);
+        }
+
+        let delta = (offset - origin).rescale(rescale::TIME_BASE, time_base);
+        super::write(packet, output, destination, time_base, delta)?;
+    }
+}
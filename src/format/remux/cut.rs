@@ -0,0 +1,292 @@
+use std::mem;
+use std::time::Duration;
+
+use crate::codec::packet::flag::Flags;
+use crate::format::context::{Input, Output};
+use crate::util::range::Range;
+use crate::{Error, Packet, Rational, Rescale, media, rescale};
+
+/// A segment copied by [`cut`], with the boundaries actually achieved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Segment {
+    /// The keyframe the copy starts on, at or before the requested start,
+    /// relative to the start of the input.
+    pub start: Duration,
+    /// The keyframe the copy stops before, at or after the requested end, or
+    /// the end of the input.
+    pub end: Duration,
+    /// Where `start` ended up in the output.
+    pub offset: Duration,
+}
+
+/// Copies `segments` of `input`, one after the other, into `output` without
+/// re-encoding, like `ffmpeg -ss <start> -to <end> -i <input> -c copy`.
+///
+/// `output` must not have streams yet: one is added for every audio, video
+/// and subtitle stream of `input`, then the header, the packets and the
+/// trailer are written. Segment bounds are relative to the start of the
+/// input; a missing start means the beginning and a missing end the end of
+/// the input.
+///
+/// Since nothing is decoded, a segment can only start on a keyframe. Cut
+/// points are taken from the reference stream, the best video stream or else
+/// the first copied one: a segment starts on its last keyframe at or before
+/// the requested start and stops before its first keyframe at or after the
+/// requested end, so the requested range is always covered. Packets of the
+/// other streams are kept between those two points, along with the packets
+/// an edit list marks as discarded, which decoders need for priming.
+///
+/// Timestamps are rebased per stream so that every segment follows the
+/// previous one and the first starts at zero. Decoding timestamps of
+/// reordered video then start below zero; depending on
+/// [`set_avoid_negative_ts`](Output::set_avoid_negative_ts) the muxer shifts
+/// them or, for MP4, records the offset in an edit list.
+///
+/// Fails with `StreamNotFound` if there is nothing to copy and with `Eof` if
+/// a segment starts past the end of the input, leaving `output` unfinished.
+pub fn cut<R: Range<Duration>>(
+    input: &mut Input,
+    output: &mut Output,
+    segments: &[R],
+) -> Result<Vec<Segment>, Error> {
+    let mapping = super::map_streams(input, output)?;
+    let reference = reference(input, &mapping).ok_or(Error::StreamNotFound)?;
+
+    let cutter = Cutter {
+        reference,
+        time_bases: input.streams().map(|stream| stream.time_base()).collect(),
+        sparse: input
+            .streams()
+            .map(|stream| stream.parameters().medium() == media::Type::Subtitle)
+            .collect(),
+        mapping,
+    };
+    let origin = super::start_time(input);
+
+    output.write_header()?;
+
+    let mut achieved = Vec::with_capacity(segments.len());
+    let mut offset = 0;
+
+    for segment in segments {
+        let start = segment
+            .start()
+            .map_or(origin, |start| origin + micros(*start));
+        let end = segment.end().map(|end| origin + micros(*end));
+        let (start, end) = cutter.copy(input, output, start, end, offset)?;
+
+        achieved.push(Segment {
+            start: super::duration(start - origin),
+            end: super::duration(end - origin),
+            offset: super::duration(offset),
+        });
+        offset += end - start;
+    }
+
+    output.write_trailer()?;
+
+    Ok(achieved)
+}
+
+fn micros(value: Duration) -> i64 {
+    i64::try_from(value.as_micros()).unwrap_or(i64::MAX / 2)
+}
+
+/// The stream whose keyframes set the cut points.
+fn reference(input: &Input, mapping: &[Option<usize>]) -> Option<usize> {
+    input
+        .streams()
+        .best(media::Type::Video)
+        .map(|stream| stream.index())
+        .filter(|&index| mapping[index].is_some())
+        .or_else(|| mapping.iter().position(Option::is_some))
+}
+
+struct Cutter {
+    mapping: Vec<Option<usize>>,
+    reference: usize,
+    time_bases: Vec<Rational>,
+    sparse: Vec<bool>,
+}
+
+impl Cutter {
+    /// Presentation time of `packet` in `AV_TIME_BASE` units.
+    fn time(&self, packet: &Packet) -> Option<i64> {
+        let time_base = self.time_bases[packet.stream()];
+
+        packet
+            .pts()
+            .or(packet.dts())
+            .map(|ts| ts.rescale(time_base, rescale::TIME_BASE))
+    }
+
+    /// Whether `packet`, of a stream other than the reference, belongs to a
+    /// segment starting at `start`.
+    fn keep(&self, packet: &Packet, start: i64) -> bool {
+        packet.flags().contains(Flags::DISCARD) || self.time(packet) >= Some(start)
+    }
+
+    fn write(&self, packet: Packet, output: &mut Output, shift: i64) -> Result<(), Error> {
+        let index = packet.stream();
+        let time_base = self.time_bases[index];
+
+        match self.mapping[index] {
+            Some(destination) => super::write(
+                packet,
+                output,
+                destination,
+                time_base,
+                shift.rescale(rescale::TIME_BASE, time_base),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes what was held back while looking for the start keyframe.
+    fn settle(
+        &self,
+        pending: &mut Vec<Packet>,
+        output: &mut Output,
+        start: i64,
+        offset: i64,
+    ) -> Result<(), Error> {
+        for packet in mem::take(pending) {
+            if packet.stream() == self.reference || self.keep(&packet, start) {
+                self.write(packet, output, offset - start)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies the segment from `start` to `end` to `offset` in the output and
+    /// returns the cut points achieved, all in `AV_TIME_BASE` units.
+    fn copy(
+        &self,
+        input: &mut Input,
+        output: &mut Output,
+        start: i64,
+        end: Option<i64>,
+        offset: i64,
+    ) -> Result<(i64, i64), Error> {
+        // Without a keyframe at or before the start, begin on the first one.
+        if input.seek(start, ..start).is_err() {
+            input.seek(start, ..)?;
+        }
+
+        let mut done: Vec<bool> = self.mapping.iter().map(Option::is_none).collect();
+        // Packets read before the start keyframe is known, or past the
+        // requested end before the end keyframe is.
+        let mut pending = Vec::new();
+        let mut candidate = None;
+        let mut first = None;
+        let mut cut_end = None;
+        let mut last = i64::MIN;
+
+        loop {
+            let mut packet = Packet::empty();
+
+            match packet.read(input) {
+                Ok(()) => (),
+                Err(Error::Eof) => break,
+                Err(e) => return Err(e),
+            }
+
+            // Streams that are not copied, or that appeared mid-file.
+            let index = packet.stream();
+            if done.get(index).copied().unwrap_or(true) {
+                continue;
+            }
+            let time = match self.time(&packet) {
+                Some(time) => time,
+                None => continue,
+            };
+            let reference = index == self.reference;
+
+            if reference {
+                let duration = packet
+                    .duration()
+                    .rescale(self.time_bases[index], rescale::TIME_BASE);
+                last = last.max(time + duration);
+            }
+
+            let start_time = match first {
+                Some(start_time) => start_time,
+                None => {
+                    if reference && packet.is_key() && (time <= start || candidate.is_none()) {
+                        pending.retain(|held| {
+                            held.stream() != self.reference && self.keep(held, time)
+                        });
+                        candidate = Some(time);
+                    } else if reference && candidate.is_none() {
+                        continue;
+                    }
+
+                    pending.push(packet);
+
+                    if reference && time > start {
+                        let start_time = candidate.unwrap_or(time);
+                        first = Some(start_time);
+                        self.settle(&mut pending, output, start_time, offset)?;
+                    }
+
+                    continue;
+                }
+            };
+
+            if reference {
+                let ends_here = packet.is_key()
+                    && time > start_time
+                    && cut_end.is_none()
+                    && end.is_some_and(|end| time >= end);
+
+                if !ends_here {
+                    self.write(packet, output, offset - start_time)?;
+                    continue;
+                }
+
+                cut_end = Some(time);
+                done[index] = true;
+
+                for held in mem::take(&mut pending) {
+                    if self.time(&held) < Some(time) {
+                        self.write(held, output, offset - start_time)?;
+                    } else {
+                        done[held.stream()] = true;
+                    }
+                }
+            } else {
+                match cut_end {
+                    Some(cut_end) if time >= cut_end => done[index] = true,
+                    None if end.is_some_and(|end| time >= end) => pending.push(packet),
+                    _ => self.write(packet, output, offset - start_time)?,
+                }
+            }
+
+            let finished = done
+                .iter()
+                .zip(&self.sparse)
+                .all(|(&done, &sparse)| done || sparse);
+
+            if cut_end.is_some() && finished {
+                break;
+            }
+        }
+
+        let start_time = match (first, candidate) {
+            (Some(start_time), _) => start_time,
+            (None, Some(start_time)) => {
+                self.settle(&mut pending, output, start_time, offset)?;
+                start_time
+            }
+            (None, None) => return Err(Error::Eof),
+        };
+
+        // The end of the input came before the requested end.
+        for held in pending {
+            self.write(held, output, offset - start_time)?;
+        }
+
+        Ok((start_time, cut_end.unwrap_or(last.max(start_time))))
+    }
+}
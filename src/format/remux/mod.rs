@@ -0,0 +1,83 @@
+//! Stream copy between containers, without decoding.
+
+pub mod cut;
+pub use self::cut::{Segment, cut};
+
+pub mod concat;
+pub use self::concat::{ConcatError, Mismatch, concat};
+
+use std::time::Duration;
+
+use crate::ffi::*;
+use crate::format::context::{Input, Output};
+use crate::{Error, Packet, Rational, Stream, codec, encoder, media};
+
+/// Whether `stream` is copied: audio, video and subtitles are, data and
+/// attachments are not.
+fn copied(stream: &Stream) -> bool {
+    matches!(
+        stream.parameters().medium(),
+        media::Type::Audio | media::Type::Video | media::Type::Subtitle
+    )
+}
+
+/// Start of `input` in `AV_TIME_BASE` units.
+fn start_time(input: &Input) -> i64 {
+    match unsafe { (*input.as_ptr()).start_time } {
+        AV_NOPTS_VALUE => 0,
+        start => start,
+    }
+}
+
+fn duration(value: i64) -> Duration {
+    Duration::from_micros(value.max(0) as u64)
+}
+
+/// Adds an output stream for every audio, video and subtitle stream of
+/// `input`, copying its codec parameters, and returns the output index of
+/// each input stream.
+fn map_streams(input: &Input, output: &mut Output) -> Result<Vec<Option<usize>>, Error> {
+    let mut mapping = Vec::with_capacity(input.nb_streams() as usize);
+
+    for stream in input.streams() {
+        if !copied(&stream) {
+            mapping.push(None);
+            continue;
+        }
+
+        let mut ost = output.add_stream(encoder::find(codec::Id::None))?;
+        // The tag is only meaningful to the container it came from; let the
+        // muxer pick its own.
+        let mut parameters = stream.parameters().clone();
+        parameters.set_codec_tag(0);
+        ost.set_parameters(parameters);
+        ost.set_time_base(stream.time_base());
+        ost.set_metadata(stream.metadata().to_owned());
+
+        mapping.push(Some(ost.index()));
+    }
+
+    Ok(mapping)
+}
+
+/// Moves `packet` from input stream time base `source` to output stream
+/// `index`, shifted by `delta` in the input time base.
+fn write(
+    mut packet: Packet,
+    output: &mut Output,
+    index: usize,
+    source: Rational,
+    delta: i64,
+) -> Result<(), Error> {
+    packet.set_pts(packet.pts().map(|pts| pts + delta));
+    packet.set_dts(packet.dts().map(|dts| dts + delta));
+    packet.set_position(-1);
+    packet.set_stream(index);
+
+    let destination = output
+        .stream(index)
+        .ok_or(Error::StreamNotFound)?
+        .time_base();
+    packet.rescale_ts(source, destination);
+    packet.write_interleaved(output)
+}
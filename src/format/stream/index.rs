@@ -0,0 +1,141 @@
+use super::Stream;
+use crate::ffi::*;
+use crate::packet::Packet;
+use libc::c_int;
+
+/// An entry of a stream's seek index (`AVIndexEntry`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    position: i64,
+    timestamp: i64,
+    size: usize,
+    flags: c_int,
+    min_distance: i32,
+}
+
+impl Entry {
+    pub unsafe fn wrap(ptr: *const AVIndexEntry) -> Self {
+        unsafe {
+            Entry {
+                position: (*ptr).pos,
+                timestamp: (*ptr).timestamp,
+                size: (*ptr).size() as usize,
+                flags: (*ptr).flags(),
+                min_distance: (*ptr).min_distance,
+            }
+        }
+    }
+
+    pub(crate) fn from_packet(packet: &Packet) -> Self {
+        Entry {
+            position: packet.position() as i64,
+            timestamp: packet.dts().or(packet.pts()).unwrap_or(AV_NOPTS_VALUE),
+            size: packet.size(),
+            flags: if packet.is_key() { AVINDEX_KEYFRAME } else { 0 },
+            min_distance: 0,
+        }
+    }
+}
+
+impl Entry {
+    /// Byte offset of the packet in the file.
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// In the stream's time base; usually the decoding timestamp.
+    pub fn timestamp(&self) -> Option<i64> {
+        match self.timestamp {
+            AV_NOPTS_VALUE => None,
+            timestamp => Some(timestamp),
+        }
+    }
+
+    /// Size of the packet in bytes, if known.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_key(&self) -> bool {
+        self.flags & AVINDEX_KEYFRAME != 0
+    }
+
+    /// Whether the packet is to be decoded but not displayed, e.g. encoder
+    /// priming before an edit list start.
+    pub fn is_discard(&self) -> bool {
+        self.flags & AVINDEX_DISCARD_FRAME != 0
+    }
+
+    /// Minimum distance, in timestamp units, between this entry and the
+    /// previous keyframe, used to avoid unneeded searching.
+    pub fn min_distance(&self) -> i32 {
+        self.min_distance
+    }
+}
+
+/// The entries the demuxer has indexed so far, in timestamp order.
+pub struct Entries<'a> {
+    stream: &'a Stream<'a>,
+    current: c_int,
+}
+
+impl<'a> Entries<'a> {
+    pub fn new<'s, 'c: 's>(stream: &'c Stream) -> Entries<'s> {
+        Entries { stream, current: 0 }
+    }
+}
+
+pub(super) unsafe fn count(stream: *const AVStream) -> c_int {
+    unsafe {
+        #[cfg(feature = "ffmpeg_5_0")]
+        {
+            avformat_index_get_entries_count(stream)
+        }
+        #[cfg(not(feature = "ffmpeg_5_0"))]
+        {
+            (*stream).nb_index_entries
+        }
+    }
+}
+
+pub(super) unsafe fn get(stream: *const AVStream, index: c_int) -> Entry {
+    unsafe {
+        #[cfg(feature = "ffmpeg_5_0")]
+        {
+            Entry::wrap(avformat_index_get_entry(stream as *mut _, index))
+        }
+        #[cfg(not(feature = "ffmpeg_5_0"))]
+        {
+            Entry::wrap((*stream).index_entries.offset(index as isize))
+        }
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Entry;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            if self.current >= count(self.stream.as_ptr()) {
+                return None;
+            }
+
+            self.current += 1;
+
+            Some(get(self.stream.as_ptr(), self.current - 1))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        unsafe {
+            let length = count(self.stream.as_ptr()) as usize;
+
+            (
+                length - self.current as usize,
+                Some(length - self.current as usize),
+            )
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for Entries<'a> {}
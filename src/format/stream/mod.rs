@@ -1,6 +1,8 @@
 pub mod disposition;
 pub use self::disposition::Disposition;
 
+pub mod index;
+
 mod stream;
 pub use self::stream::Stream;
 
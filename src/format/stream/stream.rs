@@ -1,7 +1,8 @@
-use super::Disposition;
+use super::{Disposition, index};
 use crate::codec::{self, packet};
 use crate::ffi::*;
 use crate::format::context::common::Context;
+use crate::util::display::DisplayMatrix;
 use crate::{DictionaryRef, Discard, Rational};
 use libc::c_int;
 
@@ -69,6 +70,34 @@ impl<'a> Stream<'a> {
         SideDataIter::new(self)
     }
 
+    /// The orientation the stream should be displayed in, from its
+    /// `DisplayMatrix` side data.
+    pub fn display_matrix(&self) -> Option<DisplayMatrix> {
+        self.find_side_data(
+            packet::side_data::Type::DisplayMatrix,
+            DisplayMatrix::from_bytes,
+        )
+    }
+
+    /// Decodes the stream-global side data of type `kind`, from wherever
+    /// this version of FFmpeg keeps what `StreamMut::add_side_data` sets.
+    pub(crate) fn find_side_data<T>(
+        &self,
+        kind: packet::side_data::Type,
+        decode: impl FnOnce(&[u8]) -> Option<T>,
+    ) -> Option<T> {
+        #[cfg(feature = "ffmpeg_7_0")]
+        {
+            decode(self.parameters().side_data(kind)?)
+        }
+        #[cfg(not(feature = "ffmpeg_7_0"))]
+        {
+            self.side_data()
+                .find(|side_data| side_data.kind() == kind)
+                .and_then(|side_data| decode(side_data.data()))
+        }
+    }
+
     pub fn rate(&self) -> Rational {
         unsafe { Rational::from((*self.as_ptr()).r_frame_rate) }
     }
@@ -80,6 +109,34 @@ impl<'a> Stream<'a> {
     pub fn metadata(&self) -> DictionaryRef<'_> {
         unsafe { DictionaryRef::wrap((*self.as_ptr()).metadata) }
     }
+
+    /// The seek index the demuxer has built so far, from the container's own
+    /// index (MP4 sample tables, Matroska cues, ...) or from packets read.
+    /// Formats without one yield nothing; see `Input::scan_index`.
+    pub fn index_entries(&self) -> index::Entries<'_> {
+        index::Entries::new(self)
+    }
+
+    /// The last indexed keyframe at or before `timestamp`, in the stream's
+    /// time base.
+    pub fn keyframe_before(&self, timestamp: i64) -> Option<index::Entry> {
+        self.search_index(timestamp, AVSEEK_FLAG_BACKWARD)
+    }
+
+    /// The first indexed keyframe at or after `timestamp`, in the stream's
+    /// time base.
+    pub fn keyframe_after(&self, timestamp: i64) -> Option<index::Entry> {
+        self.search_index(timestamp, 0)
+    }
+
+    fn search_index(&self, timestamp: i64, flags: c_int) -> Option<index::Entry> {
+        unsafe {
+            match av_index_search_timestamp(self.as_ptr() as *mut _, timestamp, flags) {
+                i if i < 0 => None,
+                i => Some(index::get(self.as_ptr(), i)),
+            }
+        }
+    }
 }
 
 impl<'a> PartialEq for Stream<'a> {
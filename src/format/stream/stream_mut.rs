@@ -1,10 +1,13 @@
 use std::mem;
 use std::ops::Deref;
+use std::ptr;
 
 use super::Stream;
+use crate::codec::packet::side_data;
 use crate::ffi::*;
 use crate::format::context::common::Context;
-use crate::{Dictionary, Rational, codec};
+use crate::util::display::DisplayMatrix;
+use crate::{Dictionary, Error, Rational, codec};
 
 pub struct StreamMut<'a> {
     context: &'a mut Context,
@@ -63,6 +66,52 @@ impl<'a> StreamMut<'a> {
             (*self.as_mut_ptr()).metadata = metadata;
         }
     }
+
+    /// Sets the stream-global side data of type `kind`, replacing any of the
+    /// same type, for muxers to write to the container header.
+    ///
+    /// Since FFmpeg 7.0 this side data is part of the codec parameters, so
+    /// call this after [`set_parameters`](Self::set_parameters), which
+    /// replaces it along with the rest of them.
+    pub fn add_side_data(&mut self, kind: side_data::Type, data: &[u8]) -> Result<(), Error> {
+        unsafe {
+            #[cfg(feature = "ffmpeg_7_0")]
+            let ptr = {
+                let parameters = (*self.as_mut_ptr()).codecpar;
+                let side_data = av_packet_side_data_new(
+                    &mut (*parameters).coded_side_data,
+                    &mut (*parameters).nb_coded_side_data,
+                    kind.into(),
+                    data.len(),
+                    0,
+                );
+
+                if side_data.is_null() {
+                    ptr::null_mut()
+                } else {
+                    (*side_data).data
+                }
+            };
+            #[cfg(not(feature = "ffmpeg_7_0"))]
+            let ptr = av_stream_new_side_data(self.as_mut_ptr(), kind.into(), data.len() as _);
+
+            if ptr.is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+
+            ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+        }
+
+        Ok(())
+    }
+
+    /// Sets the orientation players should display the stream in, as the
+    /// MP4 and MOV muxers write to the track header.
+    pub fn set_display_matrix(&mut self, matrix: &DisplayMatrix) -> Result<(), Error> {
+        self.add_side_data(side_data::Type::DisplayMatrix, &matrix.to_bytes())
+    }
 }
 
 impl<'a> Deref for StreamMut<'a> {
@@ -9,6 +9,8 @@ pub extern crate ffmpeg_sys_next as sys;
 #[cfg(feature = "image")]
 extern crate image;
 extern crate libc;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
 
 pub use crate::sys as ffi;
 
@@ -16,20 +18,34 @@ pub use crate::sys as ffi;
 pub mod util;
 pub use crate::util::channel_layout::{self, ChannelLayout};
 pub use crate::util::chroma;
+pub use crate::util::closed_captions;
 pub use crate::util::color;
+#[cfg(feature = "ffmpeg_5_0")]
+pub use crate::util::detection_bbox;
 pub use crate::util::dictionary;
 pub use crate::util::dictionary::Mut as DictionaryMut;
 pub use crate::util::dictionary::Owned as Dictionary;
 pub use crate::util::dictionary::Ref as DictionaryRef;
+pub use crate::util::display;
 pub use crate::util::error::{self, Error};
 pub use crate::util::frame::{self, Frame};
+pub use crate::util::hdr;
 pub use crate::util::log;
 pub use crate::util::mathematics::{self, Rescale, Rounding, rescale};
 pub use crate::util::media;
+pub use crate::util::motion_vector;
+#[cfg(feature = "ffmpeg_4_0")]
+pub use crate::util::opaque::Opaque;
 pub use crate::util::option;
 pub use crate::util::picture;
 pub use crate::util::rational::{self, Rational};
+#[cfg(feature = "ffmpeg_4_2")]
+pub use crate::util::region_of_interest;
+#[cfg(feature = "ffmpeg_4_4")]
+pub use crate::util::sei_unregistered;
 pub use crate::util::time;
+#[cfg(feature = "ffmpeg_4_3")]
+pub use crate::util::video_enc_params;
 
 #[cfg(feature = "format")]
 pub mod format;
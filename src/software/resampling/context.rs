@@ -4,7 +4,7 @@ use super::Delay;
 use crate::Dictionary;
 use crate::ffi::*;
 use crate::util::format;
-use crate::{ChannelLayout, Error, frame};
+use crate::{ChannelLayout, Error, frame, option};
 use libc::c_int;
 use std::ffi::c_void;
 
@@ -199,6 +199,20 @@ impl Context {
     }
 }
 
+unsafe impl option::Target for Context {
+    fn as_ptr(&self) -> *const c_void {
+        self.ptr as *const _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        self.ptr as *mut _
+    }
+}
+
+impl option::Settable for Context {}
+impl option::Gettable for Context {}
+impl option::Iterable for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
@@ -3,8 +3,8 @@ use std::ptr;
 use super::Flags;
 use crate::ffi::*;
 use crate::util::format;
-use crate::{Error, frame};
-use libc::c_int;
+use crate::{Error, frame, option};
+use libc::{c_int, c_void};
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub struct Definition {
@@ -164,6 +164,20 @@ impl Context {
     }
 }
 
+unsafe impl option::Target for Context {
+    fn as_ptr(&self) -> *const c_void {
+        self.ptr as *const _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        self.ptr as *mut _
+    }
+}
+
+impl option::Settable for Context {}
+impl option::Gettable for Context {}
+impl option::Iterable for Context {}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
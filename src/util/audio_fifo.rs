@@ -0,0 +1,194 @@
+use crate::ffi::*;
+use crate::util::format;
+use crate::{ChannelLayout, Error, Rational, Rescale, frame};
+use libc::{ENOMEM, c_int, c_void};
+
+/// A sample FIFO (`AVAudioFifo`) to re-block audio into frames of a fixed
+/// size, e.g. the `frame_size` an AAC or Opus encoder requires.
+///
+/// Frames of any size are [`write`](Self::write)n in and frames of the
+/// requested size are [`read`](Self::read) out, stamped with the timestamp of
+/// their first sample. At end of stream, [`flush`](Self::flush) returns the
+/// remaining, shorter frame.
+pub struct AudioFifo {
+    ptr: *mut AVAudioFifo,
+
+    format: format::Sample,
+    channel_layout: ChannelLayout,
+    rate: u32,
+    time_base: Rational,
+    pts: Option<i64>,
+    offset: i64,
+}
+
+unsafe impl Send for AudioFifo {}
+
+impl AudioFifo {
+    pub unsafe fn as_ptr(&self) -> *const AVAudioFifo {
+        self.ptr as *const _
+    }
+
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut AVAudioFifo {
+        self.ptr
+    }
+}
+
+impl AudioFifo {
+    /// Creates an empty FIFO for audio in the given format. Timestamps are
+    /// taken to be in `1/rate` until [`set_time_base`](Self::set_time_base)
+    /// says otherwise.
+    ///
+    /// Fails with `ENOMEM` if FFmpeg cannot allocate it, which includes
+    /// formats without samples and layouts without channels.
+    pub fn new(
+        format: format::Sample,
+        channel_layout: ChannelLayout,
+        rate: u32,
+    ) -> Result<Self, Error> {
+        unsafe {
+            let ptr = av_audio_fifo_alloc(format.into(), channel_layout.channels() as c_int, 1);
+
+            if ptr.is_null() {
+                return Err(Error::Other { errno: ENOMEM });
+            }
+
+            Ok(AudioFifo {
+                ptr,
+                format,
+                channel_layout,
+                rate,
+                time_base: Rational(1, rate as i32),
+                pts: None,
+                offset: 0,
+            })
+        }
+    }
+
+    pub fn format(&self) -> format::Sample {
+        self.format
+    }
+
+    pub fn channel_layout(&self) -> ChannelLayout {
+        self.channel_layout
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// The time base of the timestamps of frames going in and out.
+    pub fn time_base(&self) -> Rational {
+        self.time_base
+    }
+
+    pub fn set_time_base<R: Into<Rational>>(&mut self, value: R) {
+        self.time_base = value.into();
+    }
+
+    /// Number of samples per channel currently buffered.
+    pub fn samples(&self) -> usize {
+        unsafe { av_audio_fifo_size(self.ptr) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples() == 0
+    }
+
+    /// Appends all samples of `frame`, growing the FIFO as needed.
+    ///
+    /// The frame must match the FIFO's format and channel count. Its
+    /// timestamp is only used when the FIFO is empty; afterwards timestamps
+    /// are derived from the sample count, so gaps in the input are not
+    /// preserved.
+    pub fn write(&mut self, frame: &frame::Audio) -> Result<(), Error> {
+        if frame.format() != self.format
+            || frame.channels() as i32 != self.channel_layout.channels()
+        {
+            return Err(Error::InvalidData);
+        }
+
+        if self.is_empty() {
+            self.pts = frame.pts();
+            self.offset = 0;
+        }
+
+        unsafe {
+            match av_audio_fifo_write(
+                self.ptr,
+                (*frame.as_ptr()).extended_data as *const *mut c_void,
+                frame.samples() as c_int,
+            ) {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Takes exactly `samples` samples out of the FIFO, or returns `None` if
+    /// fewer are buffered.
+    pub fn read(&mut self, samples: usize) -> Option<frame::Audio> {
+        if samples == 0 || self.samples() < samples {
+            return None;
+        }
+
+        self.take(samples)
+    }
+
+    /// Takes whatever is left, at most `samples` samples, for the final frame
+    /// at end of stream. Returns `None` once the FIFO is empty.
+    pub fn flush(&mut self, samples: usize) -> Option<frame::Audio> {
+        let samples = samples.min(self.samples());
+
+        if samples == 0 {
+            return None;
+        }
+
+        self.take(samples)
+    }
+
+    /// Discards all buffered samples.
+    pub fn reset(&mut self) {
+        unsafe {
+            av_audio_fifo_reset(self.ptr);
+        }
+
+        self.pts = None;
+        self.offset = 0;
+    }
+
+    fn take(&mut self, samples: usize) -> Option<frame::Audio> {
+        let mut frame = frame::Audio::new(self.format, samples, self.channel_layout);
+
+        unsafe {
+            let read = av_audio_fifo_read(
+                self.ptr,
+                (*frame.as_mut_ptr()).extended_data as *const *mut c_void,
+                samples as c_int,
+            );
+
+            if read < samples as c_int {
+                return None;
+            }
+        }
+
+        // Rescale the running total rather than each chunk so rounding
+        // errors do not accumulate.
+        let sample_time_base = Rational(1, self.rate as i32);
+        frame.set_rate(self.rate);
+        frame.set_pts(
+            self.pts
+                .map(|pts| pts + self.offset.rescale(sample_time_base, self.time_base)),
+        );
+        self.offset += samples as i64;
+
+        Some(frame)
+    }
+}
+
+impl Drop for AudioFifo {
+    fn drop(&mut self) {
+        unsafe {
+            av_audio_fifo_free(self.ptr);
+        }
+    }
+}
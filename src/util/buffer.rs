@@ -0,0 +1,37 @@
+use std::panic;
+use std::process;
+
+use crate::ffi::*;
+use libc::c_void;
+
+unsafe extern "C" fn free<T>(opaque: *mut c_void, _data: *mut u8) {
+    // Dropping arbitrary user data must not unwind into FFmpeg.
+    if panic::catch_unwind(|| unsafe { drop(Box::from_raw(opaque as *mut T)) }).is_err() {
+        process::abort();
+    }
+}
+
+/// Hands `owner` over to a new `AVBufferRef` spanning `len` bytes starting at
+/// `data`. `owner` is dropped when the last reference to the buffer goes away.
+///
+/// `data` must stay valid for as long as `owner` is alive and must not move
+/// when `owner` does, which holds for heap allocations such as `Vec` or `Box`.
+pub(crate) unsafe fn create<T: Send + 'static>(
+    owner: Box<T>,
+    data: *const u8,
+    len: usize,
+    writable: bool,
+) -> *mut AVBufferRef {
+    unsafe {
+        let opaque = Box::into_raw(owner) as *mut c_void;
+        let flags = if writable { 0 } else { AV_BUFFER_FLAG_READONLY };
+
+        let buffer = av_buffer_create(data as *mut u8, len as _, Some(free::<T>), opaque, flags);
+
+        if buffer.is_null() {
+            drop(Box::from_raw(opaque as *mut T));
+        }
+
+        buffer
+    }
+}
@@ -0,0 +1,156 @@
+use std::ptr;
+
+use crate::Error;
+use crate::frame::{Frame, side_data};
+
+/// What a `cc_data` construct carries, from its `cc_type` field.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Type {
+    /// A CEA-608 byte pair of the first field (CC1/CC2).
+    Cea608Field1,
+    /// A CEA-608 byte pair of the second field (CC3/CC4).
+    Cea608Field2,
+    /// Two bytes continuing a CEA-708 DTVCC packet.
+    DtvccData,
+    /// Two bytes starting a CEA-708 DTVCC packet.
+    DtvccStart,
+}
+
+impl From<u8> for Type {
+    fn from(value: u8) -> Type {
+        match value & 3 {
+            0 => Type::Cea608Field1,
+            1 => Type::Cea608Field2,
+            2 => Type::DtvccData,
+            _ => Type::DtvccStart,
+        }
+    }
+}
+
+impl From<Type> for u8 {
+    fn from(value: Type) -> u8 {
+        match value {
+            Type::Cea608Field1 => 0,
+            Type::Cea608Field2 => 1,
+            Type::DtvccData => 2,
+            Type::DtvccStart => 3,
+        }
+    }
+}
+
+/// One `cc_data` construct of ATSC A/53: a type, a validity flag and two
+/// bytes of caption data.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Triplet {
+    pub valid: bool,
+    pub kind: Type,
+    pub data: [u8; 2],
+}
+
+impl Triplet {
+    pub fn new(kind: Type, data: [u8; 2]) -> Self {
+        Triplet {
+            valid: true,
+            kind,
+            data,
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; 3]) -> Self {
+        Triplet {
+            valid: bytes[0] & 4 != 0,
+            kind: Type::from(bytes[0]),
+            data: [bytes[1], bytes[2]],
+        }
+    }
+
+    /// The construct as transmitted, with its marker bits set.
+    pub fn to_bytes(&self) -> [u8; 3] {
+        [
+            0xf8 | ((self.valid as u8) << 2) | u8::from(self.kind),
+            self.data[0],
+            self.data[1],
+        ]
+    }
+}
+
+/// The CEA-608/708 captions of a frame, carried as `A53CC` frame side data
+/// by decoders of video with embedded captions and picked up by encoders
+/// that can embed them, such as libx264 with `a53cc` enabled.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ClosedCaptions {
+    pub triplets: Vec<Triplet>,
+}
+
+impl ClosedCaptions {
+    pub fn new(triplets: Vec<Triplet>) -> Self {
+        ClosedCaptions { triplets }
+    }
+
+    /// Decodes a sequence of `cc_data` constructs, such as the payload of
+    /// side data of type `A53CC`. A trailing partial construct is ignored.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        ClosedCaptions {
+            triplets: data
+                .chunks_exact(3)
+                .map(|bytes| Triplet::from_bytes([bytes[0], bytes[1], bytes[2]]))
+                .collect(),
+        }
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Some(Self::from_bytes(
+            frame.side_data(side_data::Type::A53CC)?.data(),
+        ))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.triplets
+            .iter()
+            .flat_map(|triplet| triplet.to_bytes())
+            .collect()
+    }
+
+    /// Sets these as the frame's `A53CC` side data, or removes it if there
+    /// are no captions.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        frame.remove_side_data(side_data::Type::A53CC);
+
+        if self.triplets.is_empty() {
+            return Ok(());
+        }
+
+        let bytes = self.to_bytes();
+        let mut side_data = frame
+            .new_side_data(side_data::Type::A53CC, bytes.len())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), (*side_data.as_mut_ptr()).data, bytes.len());
+        }
+
+        Ok(())
+    }
+
+    /// A packet for the `EIA_608` decoder (`cc_dec`), which turns CEA-608
+    /// captions into ASS subtitles; `pts` is the frame's, in the decoder's
+    /// packet time base.
+    #[cfg(feature = "codec")]
+    pub fn to_packet(&self, pts: Option<i64>) -> crate::Packet {
+        let mut packet = crate::Packet::copy(&self.to_bytes());
+        packet.set_pts(pts);
+        packet.set_dts(pts);
+
+        packet
+    }
+
+    /// The byte pairs of the valid constructs of `kind`.
+    pub fn data(&self, kind: Type) -> impl Iterator<Item = [u8; 2]> + '_ {
+        self.triplets
+            .iter()
+            .filter(move |triplet| triplet.valid && triplet.kind == kind)
+            .map(|triplet| triplet.data)
+    }
+}
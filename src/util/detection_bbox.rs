@@ -0,0 +1,256 @@
+use std::mem;
+use std::ptr;
+
+use libc::{c_char, c_int};
+
+use crate::ffi::AVRational;
+use crate::frame::{self, Frame, side_data};
+use crate::{Error, Rational};
+
+// libavutil/detection_bbox.h, which ffmpeg-sys does not bind.
+const LABEL_SIZE: usize = 64;
+const CLASSIFY: usize = 4;
+const SOURCE_SIZE: usize = 256;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVDetectionBBox {
+    x: c_int,
+    y: c_int,
+    w: c_int,
+    h: c_int,
+    detect_label: [c_char; LABEL_SIZE],
+    detect_confidence: AVRational,
+    classify_count: u32,
+    classify_labels: [[c_char; LABEL_SIZE]; CLASSIFY],
+    classify_confidences: [AVRational; CLASSIFY],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVDetectionBBoxHeader {
+    source: [c_char; SOURCE_SIZE],
+    nb_bboxes: u32,
+    bboxes_offset: usize,
+    bbox_size: usize,
+}
+
+/// A detected object, in pixels from the top left corner of the frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    /// What was detected, at most 63 bytes.
+    pub label: String,
+    pub confidence: Rational,
+    /// Up to 4 labels a classifier gave the object, with their confidence.
+    pub classifications: Vec<(String, Rational)>,
+}
+
+impl BoundingBox {
+    pub fn new<R: Into<Rational>>(
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        label: &str,
+        confidence: R,
+    ) -> Self {
+        BoundingBox {
+            x,
+            y,
+            width,
+            height,
+            label: label.to_owned(),
+            confidence: confidence.into(),
+            classifications: Vec::new(),
+        }
+    }
+
+    fn from_raw(raw: &AVDetectionBBox) -> Self {
+        let count = (raw.classify_count as usize).min(CLASSIFY);
+
+        BoundingBox {
+            x: raw.x,
+            y: raw.y,
+            width: raw.w,
+            height: raw.h,
+            label: from_c(&raw.detect_label),
+            confidence: raw.detect_confidence.into(),
+            classifications: raw.classify_labels[..count]
+                .iter()
+                .zip(&raw.classify_confidences)
+                .map(|(label, &confidence)| (from_c(label), confidence.into()))
+                .collect(),
+        }
+    }
+
+    fn to_raw(&self) -> Option<AVDetectionBBox> {
+        if self.classifications.len() > CLASSIFY {
+            return None;
+        }
+
+        let mut raw: AVDetectionBBox = unsafe { mem::zeroed() };
+        raw.x = self.x;
+        raw.y = self.y;
+        raw.w = self.width;
+        raw.h = self.height;
+        to_c(&self.label, &mut raw.detect_label)?;
+        raw.detect_confidence = self.confidence.into();
+        raw.classify_count = self.classifications.len() as u32;
+
+        for (index, (label, confidence)) in self.classifications.iter().enumerate() {
+            to_c(label, &mut raw.classify_labels[index])?;
+            raw.classify_confidences[index] = (*confidence).into();
+        }
+
+        Some(raw)
+    }
+}
+
+/// The objects detected in a frame, carried as `DETECTION_BBOXES` side data,
+/// as the `dnn_detect` and `dnn_classify` filters produce and `drawbox` with
+/// `box_source=side_data_detection_bboxes` draws.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct BoundingBoxHeader {
+    /// What produced the detections, such as the model file; at most 255
+    /// bytes.
+    pub source: String,
+    boxes: Vec<BoundingBox>,
+}
+
+impl BoundingBoxHeader {
+    pub fn new(source: &str) -> Self {
+        BoundingBoxHeader {
+            source: source.to_owned(),
+            boxes: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, value: BoundingBox) -> &mut Self {
+        self.boxes.push(value);
+        self
+    }
+
+    pub fn boxes(&self) -> &[BoundingBox] {
+        &self.boxes
+    }
+
+    /// Decodes the payload of side data of type `DETECTION_BBOXES`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let header = read::<AVDetectionBBoxHeader>(data, 0)?;
+
+        if header.bbox_size < mem::size_of::<AVDetectionBBox>() {
+            return None;
+        }
+
+        let boxes = (0..header.nb_bboxes as usize)
+            .map(|index| {
+                let offset = index
+                    .checked_mul(header.bbox_size)?
+                    .checked_add(header.bboxes_offset)?;
+
+                read::<AVDetectionBBox>(data, offset).map(|raw| BoundingBox::from_raw(&raw))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(BoundingBoxHeader {
+            source: from_c(&header.source),
+            boxes,
+        })
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(frame.side_data(side_data::Type::DETECTION_BBOXES)?.data())
+    }
+
+    /// The payload of side data carrying these detections, laid out as
+    /// `av_detection_bbox_alloc` does, or `None` if a label or the source
+    /// is too long or contains a NUL byte, or a box has more than 4
+    /// classifications.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let offset = mem::size_of::<AVDetectionBBoxHeader>()
+            .next_multiple_of(mem::align_of::<AVDetectionBBox>());
+        let size = mem::size_of::<AVDetectionBBox>();
+
+        let mut header: AVDetectionBBoxHeader = unsafe { mem::zeroed() };
+        to_c(&self.source, &mut header.source)?;
+        header.nb_bboxes = self.boxes.len() as u32;
+        header.bboxes_offset = offset;
+        header.bbox_size = size;
+
+        let mut data = vec![0; offset + size * self.boxes.len()];
+        write(&mut data, 0, header);
+
+        for (index, value) in self.boxes.iter().enumerate() {
+            write(&mut data, offset + index * size, value.to_raw()?);
+        }
+
+        Some(data)
+    }
+
+    /// Sets these as the frame's `DETECTION_BBOXES` side data.
+    ///
+    /// Fails with `EINVAL` if [`to_bytes`](Self::to_bytes) cannot represent
+    /// them, leaving the frame untouched.
+    pub fn add_to_frame(&self, frame: &mut frame::Video) -> Result<(), Error> {
+        let data = self.to_bytes().ok_or(Error::Other {
+            errno: libc::EINVAL,
+        })?;
+
+        frame.remove_side_data(side_data::Type::DETECTION_BBOXES);
+
+        let mut side_data = frame
+            .new_side_data(side_data::Type::DETECTION_BBOXES, data.len())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), (*side_data.as_mut_ptr()).data, data.len());
+        }
+
+        Ok(())
+    }
+}
+
+fn read<T: Copy>(data: &[u8], offset: usize) -> Option<T> {
+    let end = offset.checked_add(mem::size_of::<T>())?;
+    let bytes = data.get(offset..end)?;
+
+    unsafe { Some(ptr::read_unaligned(bytes.as_ptr() as *const T)) }
+}
+
+fn write<T>(data: &mut [u8], offset: usize, value: T) {
+    let bytes = &mut data[offset..offset + mem::size_of::<T>()];
+
+    unsafe { ptr::write_unaligned(bytes.as_mut_ptr() as *mut T, value) }
+}
+
+/// The NUL-terminated string in `value`, lossily converted to UTF-8.
+#[allow(clippy::unnecessary_cast)]
+fn from_c(value: &[c_char]) -> String {
+    let bytes: Vec<u8> = value
+        .iter()
+        .map(|&c| c as u8)
+        .take_while(|&c| c != 0)
+        .collect();
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Copies `value` into `target`, NUL-terminated, if it fits.
+#[allow(clippy::unnecessary_cast)]
+fn to_c(value: &str, target: &mut [c_char]) -> Option<()> {
+    if value.len() >= target.len() || value.bytes().any(|c| c == 0) {
+        return None;
+    }
+
+    for (slot, c) in target.iter_mut().zip(value.bytes()) {
+        *slot = c as c_char;
+    }
+
+    Some(())
+}
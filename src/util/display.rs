@@ -0,0 +1,118 @@
+use std::mem;
+use std::ptr;
+
+use libc::c_int;
+
+use crate::Error;
+use crate::ffi::*;
+use crate::frame::{Frame, side_data};
+
+/// The transformation to apply to decoded video for correct presentation,
+/// carried as `DisplayMatrix` frame, packet and stream side data.
+///
+/// This is a 3x3 matrix in row-major order, stored as 16.16 fixed-point
+/// values except for the last column, which is 2.30, as in the MP4 `tkhd`
+/// box. Angles are in degrees, counterclockwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayMatrix([i32; 9]);
+
+impl DisplayMatrix {
+    pub fn new(values: [i32; 9]) -> Self {
+        DisplayMatrix(values)
+    }
+
+    /// A pure counterclockwise rotation by `angle` degrees.
+    pub fn from_rotation(angle: f64) -> Self {
+        let mut matrix = DisplayMatrix([0; 9]);
+
+        unsafe {
+            av_display_rotation_set(matrix.0.as_mut_ptr(), angle);
+        }
+
+        matrix
+    }
+
+    /// Decodes the payload of side data of type `DisplayMatrix`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < mem::size_of::<[i32; 9]>() {
+            return None;
+        }
+
+        unsafe {
+            Some(DisplayMatrix(
+                ptr::read_unaligned(data.as_ptr() as *const _),
+            ))
+        }
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(frame.side_data(side_data::Type::DisplayMatrix)?.data())
+    }
+
+    /// Sets this as the frame's `DisplayMatrix` side data.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        frame.remove_side_data(side_data::Type::DisplayMatrix);
+
+        let mut side_data = frame
+            .new_side_data(side_data::Type::DisplayMatrix, mem::size_of::<[i32; 9]>())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::write_unaligned((*side_data.as_mut_ptr()).data as *mut [i32; 9], self.0);
+        }
+
+        Ok(())
+    }
+
+    pub fn values(&self) -> [i32; 9] {
+        self.0
+    }
+
+    /// The payload of side data carrying this matrix.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect()
+    }
+
+    /// The counterclockwise rotation, in degrees within `[-180, 180]`, or
+    /// `None` if the matrix is degenerate.
+    ///
+    /// Players, like the ffmpeg CLI with autorotation, turn the picture by
+    /// this angle to display it upright.
+    pub fn rotation(&self) -> Option<f64> {
+        let angle = unsafe { av_display_rotation_get(self.0.as_ptr()) };
+
+        if angle.is_nan() { None } else { Some(angle) }
+    }
+
+    /// Whether the matrix mirrors the picture, i.e. has a negative
+    /// determinant.
+    pub fn is_flipped(&self) -> bool {
+        let [a, b, _, c, d, ..] = self.0.map(i64::from);
+
+        a * d - b * c < 0
+    }
+
+    /// Adds a horizontal and/or vertical flip to the transformation.
+    pub fn flip(&mut self, horizontal: bool, vertical: bool) {
+        unsafe {
+            av_display_matrix_flip(self.0.as_mut_ptr(), horizontal as c_int, vertical as c_int);
+        }
+    }
+}
+
+impl Default for DisplayMatrix {
+    fn default() -> Self {
+        Self::from_rotation(0.0)
+    }
+}
+
+impl From<[i32; 9]> for DisplayMatrix {
+    fn from(value: [i32; 9]) -> Self {
+        DisplayMatrix(value)
+    }
+}
@@ -1,11 +1,13 @@
 use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::ptr;
 use std::slice;
 
 use super::Frame;
-use crate::ChannelLayout;
 use crate::ffi::*;
+use crate::util::buffer;
 use crate::util::format;
+use crate::{ChannelLayout, Error};
 use libc::c_int;
 
 #[derive(PartialEq, Eq)]
@@ -35,6 +37,13 @@ impl Audio {
         unsafe { Audio(Frame::empty()) }
     }
 
+    /// A new frame sharing the data of this one, unlike `clone`, which
+    /// copies it. See [`Frame::shallow_clone`].
+    #[inline]
+    pub fn shallow_clone(&self) -> Result<Self, Error> {
+        self.0.shallow_clone().map(Audio)
+    }
+
     #[inline]
     pub fn new(format: format::Sample, samples: usize, layout: ChannelLayout) -> Self {
         unsafe {
@@ -45,6 +54,84 @@ impl Audio {
         }
     }
 
+    /// Builds a frame over a caller-owned buffer without copying. Planar
+    /// formats expect the planes back to back, each `samples` samples long.
+    /// The buffer is dropped once FFmpeg releases its last reference to the
+    /// frame data.
+    pub fn from_buffer<T>(
+        format: format::Sample,
+        samples: usize,
+        layout: ChannelLayout,
+        data: T,
+    ) -> Result<Self, Error>
+    where
+        T: AsMut<[u8]> + Send + 'static,
+    {
+        unsafe {
+            let channels = layout.channels() as c_int;
+            let planes = if format.is_planar() {
+                channels as usize
+            } else {
+                1
+            };
+
+            let mut owner = Box::new(data);
+            let slice = (*owner).as_mut();
+            let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+            let mut pointers = vec![ptr::null_mut(); planes];
+            let mut linesize = 0;
+
+            match av_samples_fill_arrays(
+                pointers.as_mut_ptr(),
+                &mut linesize,
+                ptr,
+                channels,
+                samples as c_int,
+                format.into(),
+                1,
+            ) {
+                e if e < 0 => return Err(Error::from(e)),
+                size if len < size as usize => return Err(Error::InvalidData),
+                _ => (),
+            }
+
+            let buf = buffer::create(owner, ptr, len, true);
+
+            if buf.is_null() {
+                return Err(Error::Other { errno: ENOMEM });
+            }
+
+            let mut frame = Audio::empty();
+            frame.set_format(format);
+            frame.set_samples(samples);
+            frame.set_channel_layout(layout);
+
+            let raw = frame.as_mut_ptr();
+            (*raw).buf[0] = buf;
+            (*raw).linesize[0] = linesize;
+
+            let direct = planes.min(AV_NUM_DATA_POINTERS as usize);
+            (*raw).data[..direct].copy_from_slice(&pointers[..direct]);
+
+            // Planes past the eight in AVFrame.data live in extended_data,
+            // which av_frame_unref() frees when it is not AVFrame.data.
+            if planes > direct {
+                let extended = av_malloc_array(planes, mem::size_of::<*mut u8>()) as *mut *mut u8;
+
+                if extended.is_null() {
+                    return Err(Error::Other { errno: ENOMEM });
+                }
+
+                ptr::copy_nonoverlapping(pointers.as_ptr(), extended, planes);
+                (*raw).extended_data = extended;
+            } else {
+                (*raw).extended_data = (*raw).data.as_mut_ptr();
+            }
+
+            Ok(frame)
+        }
+    }
+
     #[inline]
     pub fn format(&self) -> format::Sample {
         unsafe {
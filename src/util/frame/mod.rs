@@ -4,6 +4,9 @@ pub use self::side_data::SideData;
 pub mod video;
 pub use self::video::Video;
 
+pub mod plane;
+pub use self::plane::{Plane, PlaneMut};
+
 pub mod audio;
 pub use self::audio::Audio;
 
@@ -13,7 +16,10 @@ pub use self::flag::Flags;
 pub mod decode_error;
 pub use self::decode_error::DecodeError;
 
+use crate::Error;
 use crate::ffi::*;
+#[cfg(feature = "ffmpeg_4_0")]
+use crate::util::opaque::{self, Opaque};
 use crate::{Dictionary, DictionaryRef};
 
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
@@ -167,6 +173,21 @@ impl Frame {
         unsafe { (*self.as_mut_ptr()).metadata = value.disown() }
     }
 
+    /// The user data attached with [`set_opaque`](Self::set_opaque), or by a
+    /// codec with `codec::Flags::COPY_OPAQUE` set from the packet or frame
+    /// this frame was made from.
+    #[cfg(feature = "ffmpeg_4_0")]
+    pub fn opaque(&self) -> Option<Opaque> {
+        unsafe { opaque::get((*self.as_ptr()).opaque_ref) }
+    }
+
+    /// Attaches user data to the frame, replacing any, which frame copies
+    /// and filters keep.
+    #[cfg(feature = "ffmpeg_4_0")]
+    pub fn set_opaque(&mut self, value: Option<Opaque>) -> Result<(), Error> {
+        unsafe { opaque::set(&mut (*self.as_mut_ptr()).opaque_ref, value) }
+    }
+
     #[inline]
     pub fn side_data(&self, kind: side_data::Type) -> Option<SideData<'_>> {
         unsafe {
@@ -199,6 +220,55 @@ impl Frame {
             av_frame_remove_side_data(self.as_mut_ptr(), kind.into());
         }
     }
+
+    /// A new frame referencing the same buffers and with the same
+    /// properties, without copying the data.
+    ///
+    /// The buffers are then shared: call
+    /// [`make_writable`](Self::make_writable) on either frame before
+    /// modifying its data.
+    #[inline]
+    pub fn shallow_clone(&self) -> Result<Self, Error> {
+        unsafe {
+            let mut frame = Frame::empty();
+
+            match av_frame_ref(frame.as_mut_ptr(), self.as_ptr()) {
+                0 => Ok(frame),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Whether the data can be modified in place, which is the case if the
+    /// frame holds the only reference to each of its buffers.
+    #[inline]
+    pub fn is_writable(&self) -> bool {
+        unsafe { av_frame_is_writable(self.as_ptr() as *mut _) != 0 }
+    }
+
+    /// Copies the data into buffers of its own unless it is writable
+    /// already, so that it can be modified without affecting other frames.
+    #[inline]
+    pub fn make_writable(&mut self) -> Result<(), Error> {
+        unsafe {
+            match av_frame_make_writable(self.as_mut_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
+    /// Copies everything but the data and its layout from `source`:
+    /// timestamps, flags, color properties, metadata, side data and so on.
+    #[inline]
+    pub fn copy_props(&mut self, source: &Frame) -> Result<(), Error> {
+        unsafe {
+            match av_frame_copy_props(self.as_mut_ptr(), source.as_ptr()) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
 }
 
 impl Drop for Frame {
@@ -0,0 +1,444 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayView2, ArrayViewMut2, ShapeBuilder};
+
+/// A 2D view of a plane of a video frame: rows of `width` components, each
+/// starting `stride` bytes after the previous one, with the padding between
+/// them hidden.
+///
+/// Obtained from [`Video::plane_view`](super::Video::plane_view).
+pub struct Plane<'a, T> {
+    ptr: *const u8,
+    stride: usize,
+    width: usize,
+    height: usize,
+
+    _marker: PhantomData<&'a [T]>,
+}
+
+unsafe impl<T: Sync> Send for Plane<'_, T> {}
+unsafe impl<T: Sync> Sync for Plane<'_, T> {}
+
+impl<'a, T> Plane<'a, T> {
+    /// A view of `height` rows of `width` components each, the first one at
+    /// `ptr` and the next ones `stride` bytes apart.
+    ///
+    /// Every row must be valid for reads of `width` components of type `T`
+    /// for `'a`, and neither `ptr` nor `stride` may break the alignment of
+    /// `T`.
+    pub unsafe fn from_raw_parts(
+        ptr: *const u8,
+        stride: usize,
+        width: usize,
+        height: usize,
+    ) -> Self {
+        check::<T>(ptr, stride, width, height);
+
+        Plane {
+            ptr,
+            stride,
+            width,
+            height,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of components in a row.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The distance between the starts of two rows, in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr
+    }
+
+    #[inline]
+    pub fn row(&self, y: usize) -> &'a [T] {
+        if y >= self.height {
+            panic!("out of bounds");
+        }
+
+        unsafe { slice::from_raw_parts(self.ptr.add(y * self.stride) as *const T, self.width) }
+    }
+
+    #[inline]
+    pub fn rows(&self) -> Rows<'a, T> {
+        Rows {
+            plane: *self,
+            front: 0,
+            back: self.height,
+        }
+    }
+
+    #[inline]
+    pub fn pixel(&self, x: usize, y: usize) -> &'a T {
+        &self.row(y)[x]
+    }
+
+    /// The `width` by `height` rectangle of the plane whose top left corner
+    /// is at `x`, `y`.
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Plane<'a, T> {
+        let offset = offset::<T>(self, x, y, width, height);
+
+        Plane {
+            // An empty rectangle may start past the last row.
+            ptr: self.ptr.wrapping_add(offset),
+            stride: self.stride,
+            width,
+            height,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The plane as an `ndarray` view indexed by `[y, x]`.
+    ///
+    /// Panics if the stride is not a multiple of the size of `T`, as happens
+    /// with packed formats viewed as whole pixels; view those through
+    /// [`Video::packed_array`](super::Video::packed_array) instead.
+    #[cfg(feature = "ndarray")]
+    pub fn as_array(&self) -> ArrayView2<'a, T> {
+        unsafe {
+            ArrayView2::from_shape_ptr(
+                (self.height, self.width).strides((elements::<T>(self.stride), 1)),
+                self.ptr as *const T,
+            )
+        }
+    }
+}
+
+impl<T> Clone for Plane<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Plane<'_, T> {}
+
+impl<T> fmt::Debug for Plane<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Plane")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+/// A mutable 2D view of a plane of a video frame, see [`Plane`].
+///
+/// Obtained from [`Video::plane_view_mut`](super::Video::plane_view_mut).
+pub struct PlaneMut<'a, T> {
+    ptr: *mut u8,
+    stride: usize,
+    width: usize,
+    height: usize,
+
+    _marker: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<T: Send> Send for PlaneMut<'_, T> {}
+unsafe impl<T: Sync> Sync for PlaneMut<'_, T> {}
+
+impl<'a, T> PlaneMut<'a, T> {
+    /// A view of `height` rows of `width` components each, the first one at
+    /// `ptr` and the next ones `stride` bytes apart.
+    ///
+    /// Every row must be valid for reads and writes of `width` components of
+    /// type `T` for `'a`, with nothing else accessing them, rows must not
+    /// overlap, and neither `ptr` nor `stride` may break the alignment of
+    /// `T`.
+    pub unsafe fn from_raw_parts(ptr: *mut u8, stride: usize, width: usize, height: usize) -> Self {
+        check::<T>(ptr, stride, width, height);
+
+        PlaneMut {
+            ptr,
+            stride,
+            width,
+            height,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The number of components in a row.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The distance between the starts of two rows, in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr
+    }
+
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr
+    }
+
+    /// A shared view of the same rows.
+    #[inline]
+    pub fn as_plane(&self) -> Plane<'_, T> {
+        Plane {
+            ptr: self.ptr,
+            stride: self.stride,
+            width: self.width,
+            height: self.height,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn row(&self, y: usize) -> &[T] {
+        self.as_plane().row(y)
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        if y >= self.height {
+            panic!("out of bounds");
+        }
+
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(y * self.stride) as *mut T, self.width) }
+    }
+
+    #[inline]
+    pub fn rows(&self) -> Rows<'_, T> {
+        self.as_plane().rows()
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut {
+            plane: PlaneMut {
+                ptr: self.ptr,
+                stride: self.stride,
+                width: self.width,
+                height: self.height,
+                _marker: PhantomData,
+            },
+            front: 0,
+            back: self.height,
+        }
+    }
+
+    #[inline]
+    pub fn pixel(&self, x: usize, y: usize) -> &T {
+        &self.row(y)[x]
+    }
+
+    #[inline]
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> &mut T {
+        &mut self.row_mut(y)[x]
+    }
+
+    /// The `width` by `height` rectangle of the plane whose top left corner
+    /// is at `x`, `y`.
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Plane<'_, T> {
+        self.as_plane().view(x, y, width, height)
+    }
+
+    /// The `width` by `height` rectangle of the plane whose top left corner
+    /// is at `x`, `y`, for writing.
+    pub fn view_mut(&mut self, x: usize, y: usize, width: usize, height: usize) -> PlaneMut<'_, T> {
+        let offset = offset::<T>(&self.as_plane(), x, y, width, height);
+
+        PlaneMut {
+            // An empty rectangle may start past the last row.
+            ptr: self.ptr.wrapping_add(offset),
+            stride: self.stride,
+            width,
+            height,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The plane as an `ndarray` view indexed by `[y, x]`, see
+    /// [`Plane::as_array`].
+    #[cfg(feature = "ndarray")]
+    pub fn as_array(&self) -> ArrayView2<'_, T> {
+        self.as_plane().as_array()
+    }
+
+    /// The plane as a mutable `ndarray` view indexed by `[y, x]`, see
+    /// [`Plane::as_array`].
+    #[cfg(feature = "ndarray")]
+    pub fn as_array_mut(&mut self) -> ArrayViewMut2<'_, T> {
+        self.view_mut(0, 0, self.width, self.height).into_array()
+    }
+
+    /// Turns the view into a mutable `ndarray` view indexed by `[y, x]`,
+    /// see [`Plane::as_array`].
+    #[cfg(feature = "ndarray")]
+    pub fn into_array(self) -> ArrayViewMut2<'a, T> {
+        unsafe {
+            ArrayViewMut2::from_shape_ptr(
+                (self.height, self.width).strides((elements::<T>(self.stride), 1)),
+                self.ptr as *mut T,
+            )
+        }
+    }
+}
+
+impl<T> fmt::Debug for PlaneMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PlaneMut")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+pub struct Rows<'a, T> {
+    plane: Plane<'a, T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+    type Item = &'a [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.plane.row(self.front - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.back - self.front;
+
+        (length, Some(length))
+    }
+}
+
+impl<T> DoubleEndedIterator for Rows<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.plane.row(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for Rows<'_, T> {}
+
+pub struct RowsMut<'a, T> {
+    plane: PlaneMut<'a, T>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> RowsMut<'a, T> {
+    // Rows never overlap, so handing out each of them once for `'a` is fine.
+    #[inline]
+    fn row(&mut self, y: usize) -> &'a mut [T] {
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.plane.ptr.add(y * self.plane.stride) as *mut T,
+                self.plane.width,
+            )
+        }
+    }
+}
+
+impl<'a, T> Iterator for RowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.row(self.front - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.back - self.front;
+
+        (length, Some(length))
+    }
+}
+
+impl<T> DoubleEndedIterator for RowsMut<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.row(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for RowsMut<'_, T> {}
+
+fn check<T>(ptr: *const u8, stride: usize, width: usize, height: usize) {
+    if ptr as usize % mem::align_of::<T>() != 0 || stride % mem::align_of::<T>() != 0 {
+        panic!("misaligned plane");
+    }
+
+    if height > 1 && width * mem::size_of::<T>() > stride {
+        panic!("rows overlap");
+    }
+}
+
+/// Where the rectangle starts from the start of `plane`, in bytes.
+fn offset<T>(plane: &Plane<T>, x: usize, y: usize, width: usize, height: usize) -> usize {
+    if x.checked_add(width).is_none_or(|end| end > plane.width)
+        || y.checked_add(height).is_none_or(|end| end > plane.height)
+    {
+        panic!("out of bounds");
+    }
+
+    y * plane.stride + x * mem::size_of::<T>()
+}
+
+#[cfg(feature = "ndarray")]
+fn elements<T>(stride: usize) -> usize {
+    if stride % mem::size_of::<T>() != 0 {
+        panic!("stride is not a multiple of the component size");
+    }
+
+    stride / mem::size_of::<T>()
+}
@@ -1,15 +1,20 @@
 use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::ptr;
 use std::slice;
 
-use super::Frame;
-use crate::Rational;
+use super::{Frame, Plane, PlaneMut};
 use crate::color;
 use crate::ffi::*;
 use crate::picture;
+use crate::util::buffer;
 use crate::util::chroma;
 use crate::util::format;
+use crate::util::motion_vector::MotionVectors;
+use crate::{Error, Rational};
 use libc::c_int;
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayView2, ArrayView3, ArrayViewMut2, ArrayViewMut3, ShapeBuilder};
 
 #[derive(PartialEq, Eq)]
 pub struct Video(Frame);
@@ -38,6 +43,13 @@ impl Video {
         unsafe { Video(Frame::empty()) }
     }
 
+    /// A new frame sharing the data of this one, unlike `clone`, which
+    /// copies it. See [`Frame::shallow_clone`].
+    #[inline]
+    pub fn shallow_clone(&self) -> Result<Self, Error> {
+        self.0.shallow_clone().map(Video)
+    }
+
     #[inline]
     pub fn new(format: format::Pixel, width: u32, height: u32) -> Self {
         unsafe {
@@ -48,6 +60,132 @@ impl Video {
         }
     }
 
+    /// Builds a frame over caller-owned planes without copying, one buffer
+    /// per plane together with its stride in bytes. Each buffer is dropped
+    /// once FFmpeg releases its last reference to the frame data.
+    ///
+    /// Every plane must hold at least `stride * plane_height` bytes, and the
+    /// stride must cover a full row of the plane. Buffers that are not
+    /// suitably aligned may make some SIMD code paths fall back to slower
+    /// ones.
+    pub fn from_planes<T>(
+        format: format::Pixel,
+        width: u32,
+        height: u32,
+        planes: Vec<(T, usize)>,
+    ) -> Result<Self, Error>
+    where
+        T: AsMut<[u8]> + Send + 'static,
+    {
+        unsafe {
+            let count = av_pix_fmt_count_planes(format.into());
+
+            if count < 0 || planes.len() != count as usize {
+                return Err(Error::InvalidData);
+            }
+
+            let mut frame = Video::empty();
+            frame.set_format(format);
+            frame.set_width(width);
+            frame.set_height(height);
+
+            for (index, (plane, stride)) in planes.into_iter().enumerate() {
+                // Box first so the data cannot move, even if `T` is an array.
+                let mut plane = Box::new(plane);
+                let slice = (*plane).as_mut();
+                let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+
+                if stride
+                    < av_image_get_linesize(format.into(), width as c_int, index as c_int) as usize
+                    || len < stride * plane_height(format, height, index) as usize
+                {
+                    return Err(Error::InvalidData);
+                }
+
+                let buf = buffer::create(plane, ptr, len, true);
+
+                if buf.is_null() {
+                    return Err(Error::Other { errno: ENOMEM });
+                }
+
+                (*frame.as_mut_ptr()).buf[index] = buf;
+                (*frame.as_mut_ptr()).data[index] = ptr;
+                (*frame.as_mut_ptr()).linesize[index] = stride as c_int;
+            }
+
+            Ok(frame)
+        }
+    }
+
+    /// Builds a frame over a single caller-owned buffer holding all planes
+    /// back to back, as laid out by `av_image_fill_pointers`, with the given
+    /// stride of each plane. The buffer is dropped once FFmpeg releases its
+    /// last reference to the frame data.
+    pub fn from_buffer<T>(
+        format: format::Pixel,
+        width: u32,
+        height: u32,
+        data: T,
+        strides: &[usize],
+    ) -> Result<Self, Error>
+    where
+        T: AsMut<[u8]> + Send + 'static,
+    {
+        unsafe {
+            let count = av_pix_fmt_count_planes(format.into());
+
+            if count < 0 || strides.len() != count as usize {
+                return Err(Error::InvalidData);
+            }
+
+            let mut linesizes = [0 as c_int; 4];
+
+            for (index, &stride) in strides.iter().enumerate() {
+                if stride
+                    < av_image_get_linesize(format.into(), width as c_int, index as c_int) as usize
+                {
+                    return Err(Error::InvalidData);
+                }
+
+                linesizes[index] = stride as c_int;
+            }
+
+            let mut owner = Box::new(data);
+            let slice = (*owner).as_mut();
+            let (ptr, len) = (slice.as_mut_ptr(), slice.len());
+            let mut planes = [ptr::null_mut(); 4];
+
+            match av_image_fill_pointers(
+                planes.as_mut_ptr(),
+                format.into(),
+                height as c_int,
+                ptr,
+                linesizes.as_ptr(),
+            ) {
+                e if e < 0 => return Err(Error::from(e)),
+                size if len < size as usize => return Err(Error::InvalidData),
+                _ => (),
+            }
+
+            let buf = buffer::create(owner, ptr, len, true);
+
+            if buf.is_null() {
+                return Err(Error::Other { errno: ENOMEM });
+            }
+
+            let mut frame = Video::empty();
+            frame.set_format(format);
+            frame.set_width(width);
+            frame.set_height(height);
+
+            (*frame.as_mut_ptr()).buf[0] = buf;
+            (*frame.as_mut_ptr()).data[..4].copy_from_slice(&planes);
+            (*frame.as_mut_ptr()).linesize[..4].copy_from_slice(&linesizes);
+
+            Ok(frame)
+        }
+    }
+
     #[inline]
     pub fn format(&self) -> format::Pixel {
         unsafe {
@@ -134,6 +272,57 @@ impl Video {
         }
     }
 
+    /// Pixels to crop from the top, bottom, left and right edges for
+    /// display, as signalled by the codec or container.
+    #[inline]
+    pub fn crop(&self) -> (usize, usize, usize, usize) {
+        unsafe {
+            let ptr = self.as_ptr();
+
+            (
+                (*ptr).crop_top,
+                (*ptr).crop_bottom,
+                (*ptr).crop_left,
+                (*ptr).crop_right,
+            )
+        }
+    }
+
+    #[inline]
+    pub fn set_crop(&mut self, top: usize, bottom: usize, left: usize, right: usize) {
+        unsafe {
+            let ptr = self.as_mut_ptr();
+
+            (*ptr).crop_top = top;
+            (*ptr).crop_bottom = bottom;
+            (*ptr).crop_left = left;
+            (*ptr).crop_right = right;
+        }
+    }
+
+    /// Crops the frame as [`crop`](Self::crop) says by moving the data
+    /// pointers and shrinking the dimensions, without copying, then resets
+    /// the crop.
+    ///
+    /// Unless `unaligned` is set, the left edge is rounded down so that the
+    /// data pointers keep the alignment SIMD code expects, leaving some of
+    /// it uncropped.
+    #[inline]
+    pub fn apply_cropping(&mut self, unaligned: bool) -> Result<(), Error> {
+        let flags = if unaligned {
+            AV_FRAME_CROP_UNALIGNED as c_int
+        } else {
+            0
+        };
+
+        unsafe {
+            match av_frame_apply_cropping(self.as_mut_ptr(), flags) {
+                0 => Ok(()),
+                e => Err(Error::from(e)),
+            }
+        }
+    }
+
     #[inline]
     pub fn color_space(&self) -> color::Space {
         unsafe { color::Space::from((*self.as_ptr()).colorspace) }
@@ -282,7 +471,7 @@ impl Video {
         unsafe {
             slice::from_raw_parts(
                 (*self.as_ptr()).data[index] as *const T,
-                self.stride(index) * self.plane_height(index) as usize / mem::size_of::<T>(),
+                self.plane_size(index) / mem::size_of::<T>(),
             )
         }
     }
@@ -300,7 +489,7 @@ impl Video {
         unsafe {
             slice::from_raw_parts_mut(
                 (*self.as_mut_ptr()).data[index] as *mut T,
-                self.stride(index) * self.plane_height(index) as usize / mem::size_of::<T>(),
+                self.plane_size(index) / mem::size_of::<T>(),
             )
         }
     }
@@ -311,12 +500,7 @@ impl Video {
             panic!("out of bounds");
         }
 
-        unsafe {
-            slice::from_raw_parts(
-                (*self.as_ptr()).data[index],
-                self.stride(index) * self.plane_height(index) as usize,
-            )
-        }
+        unsafe { slice::from_raw_parts((*self.as_ptr()).data[index], self.plane_size(index)) }
     }
 
     #[inline]
@@ -326,12 +510,175 @@ impl Video {
         }
 
         unsafe {
-            slice::from_raw_parts_mut(
+            slice::from_raw_parts_mut((*self.as_mut_ptr()).data[index], self.plane_size(index))
+        }
+    }
+
+    // The bytes of the plane, a whole stride for every row, but no further
+    // than the end of the buffer holding it, which cropping may have moved
+    // the plane closer to.
+    fn plane_size(&self, index: usize) -> usize {
+        let size = self.stride(index) * self.plane_height(index) as usize;
+
+        unsafe {
+            let data = (*self.as_ptr()).data[index] as usize;
+
+            for &buf in &(*self.as_ptr()).buf {
+                if buf.is_null() {
+                    continue;
+                }
+
+                let start = (*buf).data as usize;
+                let end = start + (*buf).size as usize;
+
+                if (start..end).contains(&data) {
+                    return size.min(end - data);
+                }
+            }
+        }
+
+        size
+    }
+
+    /// A 2D view of the plane, with rows as wide as the image rather than
+    /// the stride.
+    ///
+    /// Rows hold `plane_width` components for whole pixels such as `[u8; 3]`
+    /// and `plane_width` times the number of bytes per pixel for `u8`.
+    #[inline]
+    pub fn plane_view<T: Component>(&self, index: usize) -> Plane<'_, T> {
+        let width = self.row_length::<T>(index);
+
+        unsafe {
+            Plane::from_raw_parts(
+                (*self.as_ptr()).data[index],
+                self.stride(index),
+                width,
+                self.plane_height(index) as usize,
+            )
+        }
+    }
+
+    /// A mutable 2D view of the plane, see [`plane_view`](Self::plane_view).
+    #[inline]
+    pub fn plane_view_mut<T: Component>(&mut self, index: usize) -> PlaneMut<'_, T> {
+        let width = self.row_length::<T>(index);
+
+        unsafe {
+            PlaneMut::from_raw_parts(
                 (*self.as_mut_ptr()).data[index],
-                self.stride(index) * self.plane_height(index) as usize,
+                self.stride(index),
+                width,
+                self.plane_height(index) as usize,
+            )
+        }
+    }
+
+    /// The plane as an `ndarray` view indexed by `[y, x]`.
+    #[cfg(feature = "ndarray")]
+    #[inline]
+    pub fn plane_array<T: Component>(&self, index: usize) -> ArrayView2<'_, T> {
+        self.plane_view(index).as_array()
+    }
+
+    /// The plane as a mutable `ndarray` view indexed by `[y, x]`.
+    #[cfg(feature = "ndarray")]
+    #[inline]
+    pub fn plane_array_mut<T: Component>(&mut self, index: usize) -> ArrayViewMut2<'_, T> {
+        self.plane_view_mut(index).into_array()
+    }
+
+    /// The frame as an `ndarray` view indexed by `[y, x, byte]`, for packed
+    /// formats such as `RGB24` or `BGRA`, with as many bytes per pixel as
+    /// the format has.
+    ///
+    /// Panics for planar, paletted, bitstream and hardware formats.
+    #[cfg(feature = "ndarray")]
+    pub fn packed_array(&self) -> ArrayView3<'_, u8> {
+        let step = packed_step(self.format());
+
+        unsafe {
+            ArrayView3::from_shape_ptr(
+                (self.height() as usize, self.width() as usize, step).strides((
+                    self.stride(0),
+                    step,
+                    1,
+                )),
+                (*self.as_ptr()).data[0],
             )
         }
     }
+
+    /// The frame as a mutable `ndarray` view indexed by `[y, x, byte]`, see
+    /// [`packed_array`](Self::packed_array).
+    #[cfg(feature = "ndarray")]
+    pub fn packed_array_mut(&mut self) -> ArrayViewMut3<'_, u8> {
+        let step = packed_step(self.format());
+
+        unsafe {
+            ArrayViewMut3::from_shape_ptr(
+                (self.height() as usize, self.width() as usize, step).strides((
+                    self.stride(0),
+                    step,
+                    1,
+                )),
+                (*self.as_mut_ptr()).data[0],
+            )
+        }
+    }
+
+    // The number of components of type `T` a row of the plane holds.
+    fn row_length<T: Component>(&self, index: usize) -> usize {
+        if index >= self.planes() {
+            panic!("out of bounds");
+        }
+
+        if !<T as Component>::is_valid(self.format()) {
+            panic!("unsupported type");
+        }
+
+        let bytes = unsafe {
+            av_image_get_linesize(self.format().into(), self.width() as c_int, index as c_int)
+        };
+
+        bytes.max(0) as usize / mem::size_of::<T>()
+    }
+
+    /// The motion vectors the decoder exported for this frame, if asked to.
+    pub fn motion_vectors(&self) -> MotionVectors<'_> {
+        MotionVectors::from_frame(self)
+    }
+}
+
+// Logic taken from av_image_fill_pointers().
+fn plane_height(format: format::Pixel, height: u32, index: usize) -> u32 {
+    if index != 1 && index != 2 {
+        return height;
+    }
+
+    match format.descriptor() {
+        Some(desc) => {
+            let s = desc.log2_chroma_h();
+            (height + (1 << s) - 1) >> s
+        }
+        None => height,
+    }
+}
+
+// Bytes per pixel of a packed format, as laid out in its single plane.
+#[cfg(feature = "ndarray")]
+fn packed_step(format: format::Pixel) -> usize {
+    let unsupported = AV_PIX_FMT_FLAG_PLANAR
+        | AV_PIX_FMT_FLAG_PAL
+        | AV_PIX_FMT_FLAG_BITSTREAM
+        | AV_PIX_FMT_FLAG_HWACCEL;
+
+    match format.descriptor() {
+        Some(desc) if unsafe { (*desc.as_ptr()).flags & unsupported as u64 == 0 } => unsafe {
+            (*desc.as_ptr()).comp[0].step as usize
+        },
+        _ => panic!("unsupported format"),
+    }
 }
 
 impl Deref for Video {
@@ -379,6 +726,44 @@ pub unsafe trait Component {
     fn is_valid(format: format::Pixel) -> bool;
 }
 
+/// Bytes of any format with its data in memory, such as the samples of
+/// 8-bit formats.
+unsafe impl Component for u8 {
+    #[inline(always)]
+    fn is_valid(format: format::Pixel) -> bool {
+        match format.descriptor() {
+            Some(desc) => unsafe { (*desc.as_ptr()).flags & AV_PIX_FMT_FLAG_HWACCEL as u64 == 0 },
+            None => false,
+        }
+    }
+}
+
+/// Samples of formats with 9 to 16 bits per component in native byte
+/// order, such as `YUV420P10` or `RGB48`.
+unsafe impl Component for u16 {
+    #[inline(always)]
+    fn is_valid(format: format::Pixel) -> bool {
+        let Some(desc) = format.descriptor() else {
+            return false;
+        };
+
+        let foreign = if cfg!(target_endian = "big") {
+            0
+        } else {
+            AV_PIX_FMT_FLAG_BE
+        };
+
+        unsafe {
+            let desc = &*desc.as_ptr();
+
+            desc.flags & (AV_PIX_FMT_FLAG_BITSTREAM | AV_PIX_FMT_FLAG_HWACCEL | foreign) as u64 == 0
+                && desc.comp[..desc.nb_components as usize]
+                    .iter()
+                    .all(|comp| (9..=16).contains(&comp.depth))
+        }
+    }
+}
+
 #[cfg(feature = "image")]
 unsafe impl Component for ::image::Luma<u8> {
     #[inline(always)]
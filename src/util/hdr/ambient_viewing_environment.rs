@@ -0,0 +1,87 @@
+use super::{add_to_frame, read};
+use crate::ffi::AVRational;
+use crate::frame::{Frame, side_data};
+use crate::{Error, Rational};
+
+// libavutil/ambient_viewing_environment.h
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVAmbientViewingEnvironment {
+    ambient_illuminance: AVRational,
+    ambient_light_x: AVRational,
+    ambient_light_y: AVRational,
+}
+
+/// The viewing environment the content was graded for (H.274 ambient viewing
+/// environment SEI).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AmbientViewingEnvironment {
+    /// Ambient illuminance, in lux.
+    pub illuminance: Rational,
+    /// CIE 1931 xy chromaticity of the ambient light.
+    pub light: (Rational, Rational),
+}
+
+impl AmbientViewingEnvironment {
+    /// Decodes the payload of frame, packet or stream side data of type
+    /// `AMBIENT_VIEWING_ENVIRONMENT`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let raw = read::<AVAmbientViewingEnvironment>(data)?;
+
+        Some(AmbientViewingEnvironment {
+            illuminance: raw.ambient_illuminance.into(),
+            light: (raw.ambient_light_x.into(), raw.ambient_light_y.into()),
+        })
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(
+            frame
+                .side_data(side_data::Type::AMBIENT_VIEWING_ENVIRONMENT)?
+                .data(),
+        )
+    }
+
+    /// Sets this as the frame's `AMBIENT_VIEWING_ENVIRONMENT` side data.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        add_to_frame(
+            frame,
+            side_data::Type::AMBIENT_VIEWING_ENVIRONMENT,
+            self.raw(),
+        )
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+    pub fn from_parameters(parameters: &crate::codec::Parameters) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        Self::from_bytes(parameters.side_data(Type::AMBIENT_VIEWING_ENVIRONMENT)?)
+    }
+
+    #[cfg(all(feature = "format", feature = "ffmpeg_7_0"))]
+    pub fn from_stream(stream: &crate::format::stream::Stream) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        stream.find_side_data(Type::AMBIENT_VIEWING_ENVIRONMENT, Self::from_bytes)
+    }
+
+    /// Sets this as the stream's `AMBIENT_VIEWING_ENVIRONMENT` side data, for
+    /// muxers to write to the container header.
+    #[cfg(all(feature = "format", feature = "ffmpeg_7_0"))]
+    pub fn add_to_stream(
+        &self,
+        stream: &mut crate::format::stream::StreamMut,
+    ) -> Result<(), Error> {
+        use crate::codec::packet::side_data::Type;
+
+        super::add_to_stream(stream, Type::AMBIENT_VIEWING_ENVIRONMENT, self.raw())
+    }
+
+    fn raw(&self) -> AVAmbientViewingEnvironment {
+        AVAmbientViewingEnvironment {
+            ambient_illuminance: self.illuminance.into(),
+            ambient_light_x: self.light.0.into(),
+            ambient_light_y: self.light.1.into(),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+#[cfg(feature = "ffmpeg_5_0")]
+use std::mem;
+
+use super::read;
+#[cfg(feature = "ffmpeg_5_0")]
+use crate::Error;
+#[cfg(feature = "ffmpeg_5_0")]
+use crate::frame::{Frame, side_data};
+
+// libavutil/dovi_meta.h
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVDOVIDecoderConfigurationRecord {
+    dv_version_major: u8,
+    dv_version_minor: u8,
+    dv_profile: u8,
+    dv_level: u8,
+    rpu_present_flag: u8,
+    el_present_flag: u8,
+    bl_present_flag: u8,
+    dv_bl_signal_compatibility_id: u8,
+    #[cfg(feature = "ffmpeg_7_1")]
+    dv_md_compression: u8,
+}
+
+#[cfg(feature = "ffmpeg_5_0")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+struct AVDOVIMetadata {
+    header_offset: usize,
+    mapping_offset: usize,
+    color_offset: usize,
+}
+
+#[cfg(feature = "ffmpeg_5_0")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+struct AVDOVIRpuDataHeader {
+    rpu_type: u8,
+    rpu_format: u16,
+    vdr_rpu_profile: u8,
+    vdr_rpu_level: u8,
+    chroma_resampling_explicit_filter_flag: u8,
+    coef_data_type: u8,
+    coef_log2_denom: u8,
+    vdr_rpu_normalized_idc: u8,
+    bl_video_full_range_flag: u8,
+    bl_bit_depth: u8,
+    el_bit_depth: u8,
+    vdr_bit_depth: u8,
+}
+
+#[cfg(feature = "ffmpeg_5_0")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVDOVIColorMetadata {
+    dm_metadata_id: u8,
+    scene_refresh_flag: u8,
+}
+
+/// The Dolby Vision decoder configuration record of a stream (`dvcC`/`dvvC`
+/// in MP4), carried as `DOVI_CONF` packet and stream side data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct DoviConfig {
+    pub version_major: u8,
+    pub version_minor: u8,
+    pub profile: u8,
+    pub level: u8,
+    pub rpu_present: bool,
+    pub el_present: bool,
+    pub bl_present: bool,
+    pub bl_signal_compatibility_id: u8,
+    #[cfg(feature = "ffmpeg_7_1")]
+    pub md_compression: u8,
+}
+
+impl DoviConfig {
+    /// Decodes the payload of packet or stream side data of type `DOVI_CONF`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let raw = read::<AVDOVIDecoderConfigurationRecord>(data)?;
+
+        Some(DoviConfig {
+            version_major: raw.dv_version_major,
+            version_minor: raw.dv_version_minor,
+            profile: raw.dv_profile,
+            level: raw.dv_level,
+            rpu_present: raw.rpu_present_flag != 0,
+            el_present: raw.el_present_flag != 0,
+            bl_present: raw.bl_present_flag != 0,
+            bl_signal_compatibility_id: raw.dv_bl_signal_compatibility_id,
+            #[cfg(feature = "ffmpeg_7_1")]
+            md_compression: raw.dv_md_compression,
+        })
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+    pub fn from_parameters(parameters: &crate::codec::Parameters) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        Self::from_bytes(parameters.side_data(Type::DOVI_CONF)?)
+    }
+
+    #[cfg(all(feature = "format", feature = "ffmpeg_4_3"))]
+    pub fn from_stream(stream: &crate::format::stream::Stream) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        stream.find_side_data(Type::DOVI_CONF, Self::from_bytes)
+    }
+
+    /// Sets this as the stream's `DOVI_CONF` side data, for muxers to write
+    /// to the container header.
+    #[cfg(all(feature = "format", feature = "ffmpeg_4_3"))]
+    pub fn add_to_stream(
+        &self,
+        stream: &mut crate::format::stream::StreamMut,
+    ) -> Result<(), crate::Error> {
+        use crate::codec::packet::side_data::Type;
+
+        super::add_to_stream(
+            stream,
+            Type::DOVI_CONF,
+            AVDOVIDecoderConfigurationRecord {
+                dv_version_major: self.version_major,
+                dv_version_minor: self.version_minor,
+                dv_profile: self.profile,
+                dv_level: self.level,
+                rpu_present_flag: self.rpu_present as u8,
+                el_present_flag: self.el_present as u8,
+                bl_present_flag: self.bl_present as u8,
+                dv_bl_signal_compatibility_id: self.bl_signal_compatibility_id,
+                #[cfg(feature = "ffmpeg_7_1")]
+                dv_md_compression: self.md_compression,
+            },
+        )
+    }
+}
+
+/// The Dolby Vision RPU of a frame as parsed by the decoder, carried as
+/// `DOVI_METADATA` frame side data.
+///
+/// The payload is a header followed by the mapping and color metadata blocks
+/// at the offsets it records; it is kept as is so that it can be carried over
+/// to another frame untouched, with the most used fields decoded on demand.
+///
+/// This is passthrough only: there is no builder, so the only way to get one
+/// is to read it from a frame or from bytes a decoder produced.
+#[cfg(feature = "ffmpeg_5_0")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DoviMetadata {
+    data: Vec<u8>,
+    offsets: Offsets,
+}
+
+#[cfg(feature = "ffmpeg_5_0")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Offsets {
+    header: usize,
+    color: usize,
+}
+
+#[cfg(feature = "ffmpeg_5_0")]
+impl DoviMetadata {
+    /// Decodes the payload of frame side data of type `DOVI_METADATA`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let raw = read::<AVDOVIMetadata>(data)?;
+        let fits = |offset: usize, size: usize| {
+            offset
+                .checked_add(size)
+                .is_some_and(|end| end <= data.len())
+        };
+
+        if !fits(raw.header_offset, mem::size_of::<AVDOVIRpuDataHeader>())
+            || !fits(raw.color_offset, mem::size_of::<AVDOVIColorMetadata>())
+        {
+            return None;
+        }
+
+        Some(DoviMetadata {
+            data: data.to_vec(),
+            offsets: Offsets {
+                header: raw.header_offset,
+                color: raw.color_offset,
+            },
+        })
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(frame.side_data(side_data::Type::DOVI_METADATA)?.data())
+    }
+
+    /// Sets this as the frame's `DOVI_METADATA` side data.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        super::new_frame_side_data(frame, side_data::Type::DOVI_METADATA, self.data.len())?
+            .copy_from_slice(&self.data);
+
+        Ok(())
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn header(&self) -> AVDOVIRpuDataHeader {
+        read(&self.data[self.offsets.header..]).unwrap()
+    }
+
+    fn color(&self) -> AVDOVIColorMetadata {
+        read(&self.data[self.offsets.color..]).unwrap()
+    }
+
+    pub fn rpu_type(&self) -> u8 {
+        self.header().rpu_type
+    }
+
+    pub fn rpu_format(&self) -> u16 {
+        self.header().rpu_format
+    }
+
+    /// The Dolby Vision profile the RPU was written for.
+    pub fn profile(&self) -> u8 {
+        self.header().vdr_rpu_profile
+    }
+
+    pub fn level(&self) -> u8 {
+        self.header().vdr_rpu_level
+    }
+
+    pub fn bl_full_range(&self) -> bool {
+        self.header().bl_video_full_range_flag != 0
+    }
+
+    /// Bit depth of the base layer.
+    pub fn bl_bit_depth(&self) -> u8 {
+        self.header().bl_bit_depth
+    }
+
+    /// Bit depth of the enhancement layer.
+    pub fn el_bit_depth(&self) -> u8 {
+        self.header().el_bit_depth
+    }
+
+    /// Bit depth of the reconstructed signal.
+    pub fn vdr_bit_depth(&self) -> u8 {
+        self.header().vdr_bit_depth
+    }
+
+    pub fn dm_metadata_id(&self) -> u8 {
+        self.color().dm_metadata_id
+    }
+
+    /// Whether the display management metadata starts a new scene.
+    pub fn scene_refresh(&self) -> bool {
+        self.color().scene_refresh_flag != 0
+    }
+}
@@ -0,0 +1,356 @@
+use std::mem;
+#[cfg(feature = "ffmpeg_6_0")]
+use std::{ptr, slice};
+
+use libc::c_int;
+
+use super::{add_to_frame, read};
+use crate::ffi::AVRational;
+#[cfg(feature = "ffmpeg_6_0")]
+use crate::ffi::av_free;
+use crate::frame::{Frame, side_data};
+use crate::{Error, Rational};
+
+// libavutil/hdr_dynamic_metadata.h
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVHDRPlusPercentile {
+    percentage: u8,
+    percentile: AVRational,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+struct AVHDRPlusColorTransformParams {
+    window_upper_left_corner_x: AVRational,
+    window_upper_left_corner_y: AVRational,
+    window_lower_right_corner_x: AVRational,
+    window_lower_right_corner_y: AVRational,
+    center_of_ellipse_x: u16,
+    center_of_ellipse_y: u16,
+    rotation_angle: u8,
+    semimajor_axis_internal_ellipse: u16,
+    semimajor_axis_external_ellipse: u16,
+    semiminor_axis_external_ellipse: u16,
+    overlap_process_option: c_int,
+    maxscl: [AVRational; 3],
+    average_maxrgb: AVRational,
+    num_distribution_maxrgb_percentiles: u8,
+    distribution_maxrgb: [AVHDRPlusPercentile; 15],
+    fraction_bright_pixels: AVRational,
+    tone_mapping_flag: u8,
+    knee_point_x: AVRational,
+    knee_point_y: AVRational,
+    num_bezier_curve_anchors: u8,
+    bezier_curve_anchors: [AVRational; 15],
+    color_saturation_mapping_flag: u8,
+    color_saturation_weight: AVRational,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(dead_code)]
+struct AVDynamicHDRPlus {
+    itu_t_t35_country_code: u8,
+    application_version: u8,
+    num_windows: u8,
+    params: [AVHDRPlusColorTransformParams; 3],
+    targeted_system_display_maximum_luminance: AVRational,
+    targeted_system_display_actual_peak_luminance_flag: u8,
+    num_rows_targeted_system_display_actual_peak_luminance: u8,
+    num_cols_targeted_system_display_actual_peak_luminance: u8,
+    targeted_system_display_actual_peak_luminance: [[AVRational; 25]; 25],
+    mastering_display_actual_peak_luminance_flag: u8,
+    num_rows_mastering_display_actual_peak_luminance: u8,
+    num_cols_mastering_display_actual_peak_luminance: u8,
+    mastering_display_actual_peak_luminance: [[AVRational; 25]; 25],
+}
+
+// libavutil/hdr_dynamic_metadata.h, which ffmpeg-sys does not bind.
+#[cfg(feature = "ffmpeg_6_0")]
+unsafe extern "C" {
+    fn av_dynamic_hdr_plus_alloc(size: *mut usize) -> *mut AVDynamicHDRPlus;
+    fn av_dynamic_hdr_plus_from_t35(
+        s: *mut AVDynamicHDRPlus,
+        data: *const u8,
+        size: usize,
+    ) -> c_int;
+    fn av_dynamic_hdr_plus_to_t35(
+        s: *const AVDynamicHDRPlus,
+        data: *mut *mut u8,
+        size: *mut usize,
+    ) -> c_int;
+}
+
+/// The color transform parameters of one processing window. Values are
+/// normalized to `[0, 1]` unless noted otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Window {
+    /// Maximum of the linearized R, G and B components.
+    pub maxscl: [Rational; 3],
+    /// Average of the linearized maxRGB values.
+    pub average_maxrgb: Rational,
+    /// Percentages, from 0 to 100, and the maxRGB value below which that
+    /// share of pixels lies; at most 15.
+    pub distribution_maxrgb: Vec<(u8, Rational)>,
+    /// Share of pixels brighter than the brightest percentile.
+    pub fraction_bright_pixels: Rational,
+    /// Knee point of the tone mapping curve, if tone mapping is signalled.
+    pub knee_point: Option<(Rational, Rational)>,
+    /// Bézier curve anchors of the tone mapping curve; at most 15.
+    pub bezier_curve_anchors: Vec<Rational>,
+    /// Color saturation gain, in `[0, 63/8]`, if signalled.
+    pub color_saturation_weight: Option<Rational>,
+}
+
+/// SMPTE ST 2094-40 dynamic metadata (HDR10+), carried per frame.
+///
+/// Frame and stream side data hold it in FFmpeg's decoded layout; use
+/// [`to_t35`](Self::to_t35) and [`from_t35`](Self::from_t35) for the ITU-T
+/// T.35 bytes found in bitstreams and containers.
+///
+/// Window geometry and the peak luminance tables are preserved as decoded;
+/// [`set_windows`](Self::set_windows) only touches the tone mapping
+/// parameters.
+#[derive(Clone)]
+pub struct DynamicHdrPlus {
+    raw: Box<AVDynamicHDRPlus>,
+}
+
+impl DynamicHdrPlus {
+    /// Metadata for a single full-frame window, with the country code (USA)
+    /// and application version HDR10+ uses.
+    pub fn new() -> Self {
+        let mut raw: Box<AVDynamicHDRPlus> = Box::new(unsafe { mem::zeroed() });
+        raw.itu_t_t35_country_code = 0xb5;
+        raw.application_version = 1;
+        raw.num_windows = 1;
+
+        DynamicHdrPlus { raw }
+    }
+
+    /// Decodes the payload of frame side data of type `DYNAMIC_HDR_PLUS`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let mut raw = Box::new(read::<AVDynamicHDRPlus>(data)?);
+
+        // Keep the counts within the fixed-size arrays they index.
+        raw.num_windows = raw.num_windows.min(3);
+        for params in &mut raw.params {
+            params.num_distribution_maxrgb_percentiles =
+                params.num_distribution_maxrgb_percentiles.min(15);
+            params.num_bezier_curve_anchors = params.num_bezier_curve_anchors.min(15);
+        }
+
+        Some(DynamicHdrPlus { raw })
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(frame.side_data(side_data::Type::DYNAMIC_HDR_PLUS)?.data())
+    }
+
+    /// Sets this as the frame's `DYNAMIC_HDR_PLUS` side data.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        add_to_frame(frame, side_data::Type::DYNAMIC_HDR_PLUS, *self.raw)
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+    pub fn from_parameters(parameters: &crate::codec::Parameters) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        Self::from_bytes(parameters.side_data(Type::DYNAMIC_HDR10_PLUS)?)
+    }
+
+    /// Reads the stream's `DYNAMIC_HDR10_PLUS` side data, which has the same
+    /// layout as the frame side data rather than that of the bitstream.
+    #[cfg(all(feature = "format", feature = "ffmpeg_5_0"))]
+    pub fn from_stream(stream: &crate::format::stream::Stream) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        stream.find_side_data(Type::DYNAMIC_HDR10_PLUS, Self::from_bytes)
+    }
+
+    /// Sets this as the stream's `DYNAMIC_HDR10_PLUS` side data.
+    #[cfg(all(feature = "format", feature = "ffmpeg_5_0"))]
+    pub fn add_to_stream(
+        &self,
+        stream: &mut crate::format::stream::StreamMut,
+    ) -> Result<(), Error> {
+        use crate::codec::packet::side_data::Type;
+
+        super::add_to_stream(stream, Type::DYNAMIC_HDR10_PLUS, *self.raw)
+    }
+
+    /// Parses the ITU-T T.35 payload of an HDR10+ SEI message or Matroska
+    /// block addition, starting at the application mode, after the country,
+    /// provider and application identifier codes.
+    #[cfg(feature = "ffmpeg_6_0")]
+    pub fn from_t35(data: &[u8]) -> Result<Self, Error> {
+        unsafe {
+            let raw = t35_scratch()?;
+            let result = av_dynamic_hdr_plus_from_t35(raw, data.as_ptr(), data.len());
+            let mut metadata = DynamicHdrPlus {
+                raw: Box::new(*raw),
+            };
+            av_free(raw as *mut _);
+            // Only the payload after the country code is parsed.
+            metadata.raw.itu_t_t35_country_code = 0xb5;
+
+            match result {
+                e if e < 0 => Err(Error::from(e)),
+                _ => Ok(metadata),
+            }
+        }
+    }
+
+    /// Serializes the metadata to an ITU-T T.35 payload, starting at the
+    /// application mode like [`from_t35`](Self::from_t35) expects.
+    #[cfg(feature = "ffmpeg_6_0")]
+    pub fn to_t35(&self) -> Result<Vec<u8>, Error> {
+        unsafe {
+            let raw = t35_scratch()?;
+            *raw = *self.raw;
+
+            let mut data = ptr::null_mut();
+            let mut size = 0;
+            let result = av_dynamic_hdr_plus_to_t35(raw, &mut data, &mut size);
+            av_free(raw as *mut _);
+
+            match result {
+                e if e < 0 => Err(Error::from(e)),
+
+                _ => {
+                    let bytes = slice::from_raw_parts(data, size).to_vec();
+                    av_free(data as *mut _);
+
+                    Ok(bytes)
+                }
+            }
+        }
+    }
+
+    pub fn country_code(&self) -> u8 {
+        self.raw.itu_t_t35_country_code
+    }
+
+    pub fn application_version(&self) -> u8 {
+        self.raw.application_version
+    }
+
+    pub fn set_application_version(&mut self, value: u8) {
+        self.raw.application_version = value;
+    }
+
+    /// Nominal maximum luminance of the targeted display, in cd/m².
+    pub fn targeted_system_display_maximum_luminance(&self) -> Rational {
+        self.raw.targeted_system_display_maximum_luminance.into()
+    }
+
+    pub fn set_targeted_system_display_maximum_luminance<R: Into<Rational>>(&mut self, value: R) {
+        self.raw.targeted_system_display_maximum_luminance = value.into().into();
+    }
+
+    /// The processing windows, the first of which covers the whole frame.
+    pub fn windows(&self) -> Vec<Window> {
+        self.raw.params[..self.raw.num_windows as usize]
+            .iter()
+            .map(|params| Window {
+                maxscl: params.maxscl.map(Rational::from),
+                average_maxrgb: params.average_maxrgb.into(),
+                distribution_maxrgb: params.distribution_maxrgb
+                    [..params.num_distribution_maxrgb_percentiles as usize]
+                    .iter()
+                    .map(|percentile| (percentile.percentage, percentile.percentile.into()))
+                    .collect(),
+                fraction_bright_pixels: params.fraction_bright_pixels.into(),
+                knee_point: if params.tone_mapping_flag != 0 {
+                    Some((params.knee_point_x.into(), params.knee_point_y.into()))
+                } else {
+                    None
+                },
+                bezier_curve_anchors: params.bezier_curve_anchors
+                    [..params.num_bezier_curve_anchors as usize]
+                    .iter()
+                    .map(|&anchor| anchor.into())
+                    .collect(),
+                color_saturation_weight: if params.color_saturation_mapping_flag != 0 {
+                    Some(params.color_saturation_weight.into())
+                } else {
+                    None
+                },
+            })
+            .collect()
+    }
+
+    /// Replaces the processing windows.
+    ///
+    /// # Panics
+    ///
+    /// If there are no or more than 3 windows, or more than 15 percentiles or
+    /// curve anchors in one.
+    pub fn set_windows(&mut self, windows: &[Window]) {
+        assert!((1..=3).contains(&windows.len()));
+
+        self.raw.num_windows = windows.len() as u8;
+
+        for (params, window) in self.raw.params.iter_mut().zip(windows) {
+            assert!(window.distribution_maxrgb.len() <= 15);
+            assert!(window.bezier_curve_anchors.len() <= 15);
+
+            params.maxscl = window.maxscl.map(AVRational::from);
+            params.average_maxrgb = window.average_maxrgb.into();
+            params.num_distribution_maxrgb_percentiles = window.distribution_maxrgb.len() as u8;
+            for (slot, &(percentage, percentile)) in params
+                .distribution_maxrgb
+                .iter_mut()
+                .zip(&window.distribution_maxrgb)
+            {
+                slot.percentage = percentage;
+                slot.percentile = percentile.into();
+            }
+            params.fraction_bright_pixels = window.fraction_bright_pixels.into();
+
+            params.tone_mapping_flag = window.knee_point.is_some() as u8;
+            if let Some((x, y)) = window.knee_point {
+                params.knee_point_x = x.into();
+                params.knee_point_y = y.into();
+            }
+            params.num_bezier_curve_anchors = window.bezier_curve_anchors.len() as u8;
+            for (slot, &anchor) in params
+                .bezier_curve_anchors
+                .iter_mut()
+                .zip(&window.bezier_curve_anchors)
+            {
+                *slot = anchor.into();
+            }
+
+            params.color_saturation_mapping_flag = window.color_saturation_weight.is_some() as u8;
+            if let Some(weight) = window.color_saturation_weight {
+                params.color_saturation_weight = weight.into();
+            }
+        }
+    }
+}
+
+/// A zeroed `AVDynamicHDRPlus` allocated by FFmpeg, as large as the library
+/// expects even if it has grown fields this module does not mirror.
+#[cfg(feature = "ffmpeg_6_0")]
+unsafe fn t35_scratch() -> Result<*mut AVDynamicHDRPlus, Error> {
+    unsafe {
+        let raw = av_dynamic_hdr_plus_alloc(ptr::null_mut());
+
+        if raw.is_null() {
+            Err(Error::Other {
+                errno: libc::ENOMEM,
+            })
+        } else {
+            Ok(raw)
+        }
+    }
+}
+
+impl Default for DynamicHdrPlus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
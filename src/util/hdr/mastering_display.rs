@@ -0,0 +1,204 @@
+use libc::{c_int, c_uint};
+
+use super::{add_to_frame, read};
+use crate::ffi::AVRational;
+use crate::frame::{Frame, side_data};
+use crate::{Error, Rational};
+
+// libavutil/mastering_display_metadata.h
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVMasteringDisplayMetadata {
+    display_primaries: [[AVRational; 2]; 3],
+    white_point: [AVRational; 2],
+    min_luminance: AVRational,
+    max_luminance: AVRational,
+    has_primaries: c_int,
+    has_luminance: c_int,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVContentLightMetadata {
+    max_cll: c_uint,
+    max_fall: c_uint,
+}
+
+/// CIE 1931 xy chromaticity coordinates of the mastering display.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Primaries {
+    pub red: (Rational, Rational),
+    pub green: (Rational, Rational),
+    pub blue: (Rational, Rational),
+    pub white_point: (Rational, Rational),
+}
+
+/// Luminance range of the mastering display, in cd/m².
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Luminance {
+    pub min: Rational,
+    pub max: Rational,
+}
+
+/// SMPTE ST 2086 mastering display color volume, the static HDR10 metadata.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct MasteringDisplay {
+    pub primaries: Option<Primaries>,
+    pub luminance: Option<Luminance>,
+}
+
+impl MasteringDisplay {
+    /// Decodes the payload of frame, packet or stream side data of type
+    /// `MasteringDisplayMetadata`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let raw = read::<AVMasteringDisplayMetadata>(data)?;
+        let xy = |value: [AVRational; 2]| (Rational::from(value[0]), Rational::from(value[1]));
+
+        Some(MasteringDisplay {
+            primaries: if raw.has_primaries != 0 {
+                Some(Primaries {
+                    red: xy(raw.display_primaries[0]),
+                    green: xy(raw.display_primaries[1]),
+                    blue: xy(raw.display_primaries[2]),
+                    white_point: xy(raw.white_point),
+                })
+            } else {
+                None
+            },
+            luminance: if raw.has_luminance != 0 {
+                Some(Luminance {
+                    min: raw.min_luminance.into(),
+                    max: raw.max_luminance.into(),
+                })
+            } else {
+                None
+            },
+        })
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(
+            frame
+                .side_data(side_data::Type::MasteringDisplayMetadata)?
+                .data(),
+        )
+    }
+
+    /// Sets this as the frame's `MasteringDisplayMetadata` side data.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        add_to_frame(frame, side_data::Type::MasteringDisplayMetadata, self.raw())
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+    pub fn from_parameters(parameters: &crate::codec::Parameters) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        Self::from_bytes(parameters.side_data(Type::MasteringDisplayMetadata)?)
+    }
+
+    #[cfg(feature = "format")]
+    pub fn from_stream(stream: &crate::format::stream::Stream) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        stream.find_side_data(Type::MasteringDisplayMetadata, Self::from_bytes)
+    }
+
+    /// Sets this as the stream's `MasteringDisplayMetadata` side data, for
+    /// muxers to write to the container header.
+    #[cfg(feature = "format")]
+    pub fn add_to_stream(
+        &self,
+        stream: &mut crate::format::stream::StreamMut,
+    ) -> Result<(), Error> {
+        use crate::codec::packet::side_data::Type;
+
+        super::add_to_stream(stream, Type::MasteringDisplayMetadata, self.raw())
+    }
+
+    fn raw(&self) -> AVMasteringDisplayMetadata {
+        let zero = AVRational { num: 0, den: 1 };
+        let xy = |(x, y): (Rational, Rational)| [x.into(), y.into()];
+
+        AVMasteringDisplayMetadata {
+            display_primaries: match self.primaries {
+                Some(primaries) => [xy(primaries.red), xy(primaries.green), xy(primaries.blue)],
+                None => [[zero; 2]; 3],
+            },
+            white_point: self
+                .primaries
+                .map_or([zero; 2], |primaries| xy(primaries.white_point)),
+            min_luminance: self
+                .luminance
+                .map_or(zero, |luminance| luminance.min.into()),
+            max_luminance: self
+                .luminance
+                .map_or(zero, |luminance| luminance.max.into()),
+            has_primaries: self.primaries.is_some() as c_int,
+            has_luminance: self.luminance.is_some() as c_int,
+        }
+    }
+}
+
+/// CTA-861.3 content light level, in cd/m².
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct ContentLightLevel {
+    /// Maximum content light level (MaxCLL).
+    pub max: u32,
+    /// Maximum frame-average light level (MaxFALL).
+    pub max_frame_average: u32,
+}
+
+impl ContentLightLevel {
+    /// Decodes the payload of frame, packet or stream side data of type
+    /// `ContentLightLevel`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let raw = read::<AVContentLightMetadata>(data)?;
+
+        Some(ContentLightLevel {
+            max: raw.max_cll,
+            max_frame_average: raw.max_fall,
+        })
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        Self::from_bytes(frame.side_data(side_data::Type::ContentLightLevel)?.data())
+    }
+
+    /// Sets this as the frame's `ContentLightLevel` side data.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        add_to_frame(frame, side_data::Type::ContentLightLevel, self.raw())
+    }
+
+    #[cfg(all(feature = "codec", feature = "ffmpeg_7_0"))]
+    pub fn from_parameters(parameters: &crate::codec::Parameters) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        Self::from_bytes(parameters.side_data(Type::ContentLightLevel)?)
+    }
+
+    #[cfg(feature = "format")]
+    pub fn from_stream(stream: &crate::format::stream::Stream) -> Option<Self> {
+        use crate::codec::packet::side_data::Type;
+
+        stream.find_side_data(Type::ContentLightLevel, Self::from_bytes)
+    }
+
+    /// Sets this as the stream's `ContentLightLevel` side data, for muxers to
+    /// write to the container header.
+    #[cfg(feature = "format")]
+    pub fn add_to_stream(
+        &self,
+        stream: &mut crate::format::stream::StreamMut,
+    ) -> Result<(), Error> {
+        use crate::codec::packet::side_data::Type;
+
+        super::add_to_stream(stream, Type::ContentLightLevel, self.raw())
+    }
+
+    fn raw(&self) -> AVContentLightMetadata {
+        AVContentLightMetadata {
+            max_cll: self.max,
+            max_fall: self.max_frame_average,
+        }
+    }
+}
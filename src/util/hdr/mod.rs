@@ -0,0 +1,87 @@
+//! Typed HDR metadata, as carried in frame, packet and stream side data.
+//!
+//! ffmpeg-sys does not bind the libavutil headers describing these payloads,
+//! so each module mirrors the `#[repr(C)]` layout it reads and writes. FFmpeg
+//! only ever appends fields to them.
+
+pub mod mastering_display;
+pub use self::mastering_display::{ContentLightLevel, Luminance, MasteringDisplay, Primaries};
+
+#[cfg(feature = "ffmpeg_4_2")]
+pub mod dynamic_hdr_plus;
+#[cfg(feature = "ffmpeg_4_2")]
+pub use self::dynamic_hdr_plus::DynamicHdrPlus;
+
+pub mod dovi;
+pub use self::dovi::DoviConfig;
+#[cfg(feature = "ffmpeg_5_0")]
+pub use self::dovi::DoviMetadata;
+
+#[cfg(feature = "ffmpeg_6_0")]
+pub mod ambient_viewing_environment;
+#[cfg(feature = "ffmpeg_6_0")]
+pub use self::ambient_viewing_environment::AmbientViewingEnvironment;
+
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use crate::Error;
+use crate::frame::{Frame, side_data};
+
+/// Reads a `T` from the start of a side data payload, if it is large enough.
+fn read<T: Copy>(data: &[u8]) -> Option<T> {
+    if data.len() < mem::size_of::<T>() {
+        return None;
+    }
+
+    unsafe { Some(ptr::read_unaligned(data.as_ptr() as *const T)) }
+}
+
+/// Writes `value` into `data`, which must be exactly as large.
+fn write<T>(data: &mut [u8], value: T) {
+    assert_eq!(data.len(), mem::size_of::<T>());
+
+    unsafe { ptr::write_unaligned(data.as_mut_ptr() as *mut T, value) }
+}
+
+/// Replaces the side data of type `kind` on `frame` with zeroed space for a
+/// `T` and writes `value` there.
+fn add_to_frame<T>(frame: &mut Frame, kind: side_data::Type, value: T) -> Result<(), Error> {
+    let data = new_frame_side_data(frame, kind, mem::size_of::<T>())?;
+    write(data, value);
+
+    Ok(())
+}
+
+fn new_frame_side_data(
+    frame: &mut Frame,
+    kind: side_data::Type,
+    size: usize,
+) -> Result<&mut [u8], Error> {
+    frame.remove_side_data(kind);
+
+    unsafe {
+        let mut side_data = frame.new_side_data(kind, size).ok_or(Error::Other {
+            errno: libc::ENOMEM,
+        })?;
+        let data = (*side_data.as_mut_ptr()).data;
+        ptr::write_bytes(data, 0, size);
+
+        Ok(slice::from_raw_parts_mut(data, size))
+    }
+}
+
+/// Replaces the stream-global side data of type `kind` on `stream` with
+/// `value`.
+#[cfg(feature = "format")]
+fn add_to_stream<T>(
+    stream: &mut crate::format::stream::StreamMut,
+    kind: crate::codec::packet::side_data::Type,
+    value: T,
+) -> Result<(), Error> {
+    let mut data = vec![0; mem::size_of::<T>()];
+    write(&mut data, value);
+
+    stream.add_side_data(kind, &data)
+}
@@ -0,0 +1,251 @@
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Once, RwLock};
+
+use super::Level;
+use crate::ffi::*;
+use libc::{c_char, c_int, c_void};
+
+// `va_list` decays to a pointer in parameter position on x86_64 SysV, which
+// bindgen spells out; everywhere else the typedef is used as is.
+#[cfg(all(target_arch = "x86_64", not(windows)))]
+type VaList = *mut __va_list_tag;
+#[cfg(not(all(target_arch = "x86_64", not(windows))))]
+type VaList = va_list;
+
+const LINE_SIZE: usize = 1024;
+
+type Callback = dyn Fn(&Message) + Send + Sync;
+
+static CALLBACK: RwLock<Option<Arc<Callback>>> = RwLock::new(None);
+static INSTALL: Once = Once::new();
+
+thread_local! {
+    static CAPTURES: RefCell<Vec<Vec<Message>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A single message emitted through `av_log`.
+#[derive(Clone, Debug)]
+pub struct Message {
+    level: Level,
+    item_name: Option<String>,
+    text: String,
+}
+
+impl Message {
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Name of the `AVClass` instance that emitted the message, e.g. `"h264"`
+    /// or `"mov,mp4,m4a,3gp,3g2,mj2"`, if it was emitted on behalf of one.
+    pub fn item_name(&self) -> Option<&str> {
+        self.item_name.as_deref()
+    }
+
+    /// The formatted message, without the `[name @ 0x...]` prefix. FFmpeg
+    /// sometimes emits a line in several pieces, so only the last one ends
+    /// with a newline.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Routes every message that passes the [`set_level`](super::set_level)
+/// threshold to `callback` instead of stderr.
+///
+/// The callback may be invoked from any thread, including FFmpeg's internal
+/// worker threads. A panic inside it is caught and the message dropped.
+pub fn set_callback<F>(callback: F)
+where
+    F: Fn(&Message) + Send + Sync + 'static,
+{
+    *CALLBACK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(callback));
+    install();
+}
+
+/// Restores FFmpeg's default output to stderr.
+pub fn reset_callback() {
+    *CALLBACK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Runs `f` and returns, alongside its result, every message emitted on the
+/// current thread while it ran, e.g. to attach the warnings of opening and
+/// reading one input to that job's error report.
+///
+/// Captured messages are still passed to the callback if one is set, but no
+/// longer printed to stderr otherwise. Messages emitted from FFmpeg's own
+/// worker threads, such as frame-threaded decoders, are not captured.
+pub fn capture<T, F>(f: F) -> (T, Vec<Message>)
+where
+    F: FnOnce() -> T,
+{
+    struct Scope;
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            CAPTURES.with(|captures| captures.borrow_mut().pop());
+        }
+    }
+
+    install();
+    CAPTURES.with(|captures| captures.borrow_mut().push(Vec::new()));
+
+    let scope = Scope;
+    let value = f();
+    let messages = CAPTURES.with(|captures| {
+        captures
+            .borrow_mut()
+            .last_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    });
+    drop(scope);
+
+    (value, messages)
+}
+
+/// Forwards FFmpeg's messages to the `log` crate under the `ffmpeg` target.
+#[cfg(feature = "log")]
+pub fn forward_to_log() {
+    set_callback(|message| {
+        let level = match message.level() {
+            Level::Quiet | Level::Panic | Level::Fatal | Level::Error => ::log::Level::Error,
+            Level::Warning => ::log::Level::Warn,
+            Level::Info => ::log::Level::Info,
+            Level::Verbose | Level::Debug => ::log::Level::Debug,
+            Level::Trace => ::log::Level::Trace,
+        };
+
+        ::log::log!(
+            target: "ffmpeg",
+            level,
+            "[{}] {}",
+            message.item_name().unwrap_or("ffmpeg"),
+            message.text().trim_end()
+        );
+    });
+}
+
+/// Forwards FFmpeg's messages to `tracing` as events with the `ffmpeg`
+/// target and an `item` field holding the emitting `AVClass` item name.
+#[cfg(feature = "tracing")]
+pub fn forward_to_tracing() {
+    set_callback(|message| {
+        let item = message.item_name().unwrap_or("ffmpeg");
+        let text = message.text().trim_end();
+
+        match message.level() {
+            Level::Quiet | Level::Panic | Level::Fatal | Level::Error => {
+                ::tracing::error!(target: "ffmpeg", item, "{}", text)
+            }
+            Level::Warning => ::tracing::warn!(target: "ffmpeg", item, "{}", text),
+            Level::Info => ::tracing::info!(target: "ffmpeg", item, "{}", text),
+            Level::Verbose | Level::Debug => {
+                ::tracing::debug!(target: "ffmpeg", item, "{}", text)
+            }
+            Level::Trace => ::tracing::trace!(target: "ffmpeg", item, "{}", text),
+        }
+    });
+}
+
+fn install() {
+    INSTALL.call_once(|| unsafe { av_log_set_callback(Some(callback)) });
+}
+
+fn level(value: c_int) -> Level {
+    match value {
+        v if v <= AV_LOG_PANIC => Level::Panic,
+        v if v <= AV_LOG_FATAL => Level::Fatal,
+        v if v <= AV_LOG_ERROR => Level::Error,
+        v if v <= AV_LOG_WARNING => Level::Warning,
+        v if v <= AV_LOG_INFO => Level::Info,
+        v if v <= AV_LOG_VERBOSE => Level::Verbose,
+        v if v <= AV_LOG_DEBUG => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+unsafe fn item_name(avcl: *mut c_void) -> Option<String> {
+    unsafe {
+        if avcl.is_null() {
+            return None;
+        }
+
+        let class = *(avcl as *const *const AVClass);
+
+        if class.is_null() {
+            return None;
+        }
+
+        let name = match (*class).item_name {
+            Some(item_name) => item_name(avcl),
+            None => (*class).class_name,
+        };
+
+        if name.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+        }
+    }
+}
+
+unsafe extern "C" fn callback(avcl: *mut c_void, level: c_int, fmt: *const c_char, vl: VaList) {
+    unsafe {
+        // The bits above the level carry colors and tags, see `AV_LOG_C`.
+        let severity = level & 0xff;
+
+        if severity > av_log_get_level() {
+            return;
+        }
+
+        let target = CALLBACK.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let capturing = CAPTURES
+            .try_with(|captures| captures.try_borrow().is_ok_and(|c| !c.is_empty()))
+            .unwrap_or(false);
+
+        // The arguments can only be consumed once, so let FFmpeg handle the
+        // common case untouched.
+        if target.is_none() && !capturing {
+            av_log_default_callback(avcl, level, fmt, vl);
+            return;
+        }
+
+        let mut line = [0 as c_char; LINE_SIZE];
+        let mut print_prefix = 0;
+        av_log_format_line2(
+            avcl,
+            level,
+            fmt,
+            vl,
+            line.as_mut_ptr(),
+            LINE_SIZE as c_int,
+            &mut print_prefix,
+        );
+
+        let message = Message {
+            level: self::level(severity),
+            item_name: item_name(avcl),
+            text: CStr::from_ptr(line.as_ptr()).to_string_lossy().into_owned(),
+        };
+
+        if capturing {
+            let _ = CAPTURES.try_with(|captures| {
+                if let Some(current) = captures
+                    .try_borrow_mut()
+                    .ok()
+                    .as_mut()
+                    .and_then(|captures| captures.last_mut())
+                {
+                    current.push(message.clone());
+                }
+            });
+        }
+
+        if let Some(target) = target {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| target(&message)));
+        }
+    }
+}
@@ -4,6 +4,13 @@ pub use self::level::Level;
 pub mod flag;
 pub use self::flag::Flags;
 
+pub mod callback;
+#[cfg(feature = "log")]
+pub use self::callback::forward_to_log;
+#[cfg(feature = "tracing")]
+pub use self::callback::forward_to_tracing;
+pub use self::callback::{Message, capture, reset_callback, set_callback};
+
 use crate::ffi::*;
 use std::convert::TryInto;
 
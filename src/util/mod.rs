@@ -1,19 +1,35 @@
 #[macro_use]
 pub mod dictionary;
+pub mod audio_fifo;
+pub(crate) mod buffer;
 pub mod chroma;
+pub mod closed_captions;
 pub mod color;
+#[cfg(feature = "ffmpeg_5_0")]
+pub mod detection_bbox;
+pub mod display;
 pub mod error;
 pub mod format;
 pub mod frame;
+pub mod hdr;
 pub mod interrupt;
 pub mod log;
 pub mod mathematics;
 pub mod media;
+pub mod motion_vector;
+#[cfg(feature = "ffmpeg_4_0")]
+pub mod opaque;
 pub mod option;
 pub mod picture;
 pub mod range;
 pub mod rational;
+#[cfg(feature = "ffmpeg_4_2")]
+pub mod region_of_interest;
+#[cfg(feature = "ffmpeg_4_4")]
+pub mod sei_unregistered;
 pub mod time;
+#[cfg(feature = "ffmpeg_4_3")]
+pub mod video_enc_params;
 
 #[cfg_attr(feature = "ffmpeg_7_0", path = "channel_layout.rs")]
 #[cfg_attr(not(feature = "ffmpeg_7_0"), path = "legacy_channel_layout.rs")]
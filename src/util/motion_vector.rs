@@ -0,0 +1,115 @@
+use std::fmt;
+use std::mem;
+use std::ptr;
+
+use crate::ffi::*;
+use crate::frame::{Frame, side_data};
+
+/// A block motion vector exported by a decoder, from `MotionVectors` frame
+/// side data.
+///
+/// Decoders export these with `decoder::ExportData::MVS` set, or
+/// `codec::Flags2::EXPORT_MVS` with older versions of FFmpeg.
+#[derive(Copy, Clone)]
+pub struct MotionVector(AVMotionVector);
+
+impl MotionVector {
+    /// Where the block is predicted from: negative for a past frame and
+    /// positive for a future one. Decoders only export the direction, as
+    /// `-1` or `1`.
+    pub fn source(&self) -> i32 {
+        self.0.source
+    }
+
+    /// Width and height of the block.
+    pub fn size(&self) -> (u8, u8) {
+        (self.0.w, self.0.h)
+    }
+
+    /// Center of the block in the reference frame. May lie outside of it.
+    pub fn src(&self) -> (i16, i16) {
+        (self.0.src_x, self.0.src_y)
+    }
+
+    /// Center of the block in the current frame. May lie outside of it.
+    pub fn dst(&self) -> (i16, i16) {
+        (self.0.dst_x, self.0.dst_y)
+    }
+
+    /// The motion vector, in units of `1 / scale` pixels, such that
+    /// `src = dst + motion / scale`.
+    pub fn motion(&self) -> (i32, i32) {
+        (self.0.motion_x, self.0.motion_y)
+    }
+
+    pub fn scale(&self) -> u16 {
+        self.0.motion_scale
+    }
+}
+
+impl fmt::Debug for MotionVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MotionVector")
+            .field("source", &self.source())
+            .field("size", &self.size())
+            .field("src", &self.src())
+            .field("dst", &self.dst())
+            .field("motion", &self.motion())
+            .field("scale", &self.scale())
+            .finish()
+    }
+}
+
+/// The motion vectors of a frame, in the order the decoder exported them.
+#[derive(Clone)]
+pub struct MotionVectors<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MotionVectors<'a> {
+    /// Iterates over the payload of side data of type `MotionVectors`.
+    pub fn new(data: &'a [u8]) -> Self {
+        let size = data.len() - data.len() % mem::size_of::<AVMotionVector>();
+
+        MotionVectors {
+            data: &data[..size],
+        }
+    }
+
+    /// The motion vectors attached to `frame`, if any.
+    pub fn from_frame(frame: &'a Frame) -> Self {
+        Self::new(
+            frame
+                .side_data(side_data::Type::MotionVectors)
+                .map_or(&[], |side_data| side_data.data()),
+        )
+    }
+}
+
+impl<'a> Iterator for MotionVectors<'a> {
+    type Item = MotionVector;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let (head, tail) = self.data.split_at(mem::size_of::<AVMotionVector>());
+        self.data = tail;
+
+        // Side data buffers are aligned, but nothing guarantees it.
+        unsafe {
+            Some(MotionVector(ptr::read_unaligned(
+                head.as_ptr() as *const AVMotionVector
+            )))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.data.len() / mem::size_of::<AVMotionVector>();
+
+        (length, Some(length))
+    }
+}
+
+impl<'a> ExactSizeIterator for MotionVectors<'a> {}
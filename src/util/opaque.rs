@@ -0,0 +1,75 @@
+use std::any::Any;
+use std::mem;
+use std::sync::Arc;
+
+use crate::Error;
+use crate::ffi::*;
+use crate::util::buffer;
+use libc::c_void;
+
+/// User data carried by packets and frames through their `opaque_ref`, and
+/// from one to the other by codecs with `codec::Flags::COPY_OPAQUE` set.
+pub type Opaque = Arc<dyn Any + Send + Sync>;
+
+// Tells our `opaque_ref` buffers apart from any other.
+const MAGIC: u64 = u64::from_be_bytes(*b"rsopaque");
+
+struct Payload {
+    magic: u64,
+    value: Opaque,
+}
+
+/// A read-only buffer holding `value`, to set as an `opaque_ref`.
+pub(crate) fn wrap(value: Opaque) -> *mut AVBufferRef {
+    let payload = Box::new(Payload {
+        magic: MAGIC,
+        value,
+    });
+    let data = &*payload as *const Payload as *const u8;
+
+    unsafe { buffer::create(payload, data, mem::size_of::<Payload>(), false) }
+}
+
+/// The value of an `opaque_ref` made by [`wrap`], or `None` for buffers that
+/// were not.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) unsafe fn get(buffer: *const AVBufferRef) -> Option<Opaque> {
+    unsafe {
+        if buffer.is_null()
+            || (*buffer).size as usize != mem::size_of::<Payload>()
+            || av_buffer_get_opaque(buffer) != (*buffer).data as *mut c_void
+        {
+            return None;
+        }
+
+        let payload = &*((*buffer).data as *const Payload);
+
+        if payload.magic == MAGIC {
+            Some(payload.value.clone())
+        } else {
+            None
+        }
+    }
+}
+
+/// Replaces the `opaque_ref` at `target` with a buffer holding `value`.
+pub(crate) unsafe fn set(
+    target: *mut *mut AVBufferRef,
+    value: Option<Opaque>,
+) -> Result<(), Error> {
+    unsafe {
+        av_buffer_unref(target);
+
+        if let Some(value) = value {
+            *target = wrap(value);
+
+            if (*target).is_null() {
+                return Err(Error::Other {
+                    errno: libc::ENOMEM,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
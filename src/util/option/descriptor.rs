@@ -0,0 +1,282 @@
+use std::ffi::CStr;
+use std::ptr;
+use std::str::from_utf8_unchecked;
+
+use super::{Flags, Type};
+use crate::ffi::AVOptionType::*;
+use crate::ffi::*;
+use libc::c_void;
+
+/// An `AVClass`, i.e. the static description of an options-enabled object.
+#[derive(Copy, Clone)]
+pub struct Class {
+    ptr: *const AVClass,
+}
+
+unsafe impl Send for Class {}
+unsafe impl Sync for Class {}
+
+impl Class {
+    pub unsafe fn wrap(ptr: *const AVClass) -> Self {
+        Class { ptr }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVClass {
+        self.ptr
+    }
+}
+
+impl Class {
+    pub fn name(&self) -> &str {
+        unsafe { from_utf8_unchecked(CStr::from_ptr((*self.as_ptr()).class_name).to_bytes()) }
+    }
+
+    /// The options declared by this class, including named constants.
+    pub fn options(&self) -> DescriptorIter {
+        DescriptorIter::new(self.ptr, None)
+    }
+
+    /// The classes of every child object this class may have, e.g. the private
+    /// classes of all codecs for the `AVCodecContext` class.
+    pub fn children(&self) -> ClassIter {
+        ClassIter::new(self.ptr)
+    }
+}
+
+/// The value of an option, as stored in `AVOption.default_val`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Value<'a> {
+    Int(i64),
+    Double(f64),
+    String(Option<&'a str>),
+    None,
+}
+
+/// An `AVOption`: name, documentation, type, default and range of a single
+/// option.
+#[derive(Copy, Clone)]
+pub struct Descriptor {
+    ptr: *const AVOption,
+    class: *const AVClass,
+}
+
+unsafe impl Send for Descriptor {}
+unsafe impl Sync for Descriptor {}
+
+impl Descriptor {
+    pub unsafe fn wrap(ptr: *const AVOption, class: *const AVClass) -> Self {
+        Descriptor { ptr, class }
+    }
+
+    pub unsafe fn as_ptr(&self) -> *const AVOption {
+        self.ptr
+    }
+}
+
+impl Descriptor {
+    pub fn name(&self) -> &str {
+        unsafe { from_utf8_unchecked(CStr::from_ptr((*self.as_ptr()).name).to_bytes()) }
+    }
+
+    pub fn help(&self) -> Option<&str> {
+        unsafe {
+            let ptr = (*self.as_ptr()).help;
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(from_utf8_unchecked(CStr::from_ptr(ptr).to_bytes()))
+            }
+        }
+    }
+
+    pub fn kind(&self) -> Type {
+        unsafe { Type::from((*self.as_ptr()).type_) }
+    }
+
+    pub fn flags(&self) -> Flags {
+        unsafe { Flags::from_bits_truncate((*self.as_ptr()).flags) }
+    }
+
+    /// The unit grouping this option with its named constants.
+    pub fn unit(&self) -> Option<&str> {
+        unsafe {
+            let ptr = (*self.as_ptr()).unit;
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(from_utf8_unchecked(CStr::from_ptr(ptr).to_bytes()))
+            }
+        }
+    }
+
+    pub fn default_value(&self) -> Value<'_> {
+        unsafe {
+            let value = &(*self.as_ptr()).default_val;
+
+            match (*self.as_ptr()).type_ {
+                AV_OPT_TYPE_FLAGS
+                | AV_OPT_TYPE_INT
+                | AV_OPT_TYPE_INT64
+                | AV_OPT_TYPE_UINT64
+                | AV_OPT_TYPE_CONST
+                | AV_OPT_TYPE_BOOL
+                | AV_OPT_TYPE_PIXEL_FMT
+                | AV_OPT_TYPE_SAMPLE_FMT
+                | AV_OPT_TYPE_DURATION => Value::Int(value.i64_),
+
+                #[cfg(feature = "ffmpeg_7_1")]
+                AV_OPT_TYPE_UINT => Value::Int(value.i64_),
+
+                #[cfg(not(feature = "ffmpeg_7_0"))]
+                AV_OPT_TYPE_CHANNEL_LAYOUT => Value::Int(value.i64_),
+
+                AV_OPT_TYPE_DOUBLE | AV_OPT_TYPE_FLOAT | AV_OPT_TYPE_RATIONAL => {
+                    Value::Double(value.dbl)
+                }
+
+                AV_OPT_TYPE_STRING
+                | AV_OPT_TYPE_IMAGE_SIZE
+                | AV_OPT_TYPE_VIDEO_RATE
+                | AV_OPT_TYPE_COLOR
+                | AV_OPT_TYPE_DICT => {
+                    if value.str_.is_null() {
+                        Value::String(None)
+                    } else {
+                        Value::String(Some(from_utf8_unchecked(
+                            CStr::from_ptr(value.str_).to_bytes(),
+                        )))
+                    }
+                }
+
+                #[cfg(feature = "ffmpeg_5_1")]
+                AV_OPT_TYPE_CHLAYOUT => {
+                    if value.str_.is_null() {
+                        Value::String(None)
+                    } else {
+                        Value::String(Some(from_utf8_unchecked(
+                            CStr::from_ptr(value.str_).to_bytes(),
+                        )))
+                    }
+                }
+
+                _ => Value::None,
+            }
+        }
+    }
+
+    pub fn min(&self) -> f64 {
+        unsafe { (*self.as_ptr()).min }
+    }
+
+    pub fn max(&self) -> f64 {
+        unsafe { (*self.as_ptr()).max }
+    }
+
+    /// The named constants that may be used as values of this option, e.g.
+    /// `"fast"` or `"slow"` for a preset.
+    pub fn constants(&self) -> DescriptorIter {
+        match self.kind() {
+            Type::Constant => DescriptorIter::new(ptr::null(), None),
+            _ => DescriptorIter::new(self.class, Some(*self)),
+        }
+    }
+}
+
+pub struct DescriptorIter {
+    class: *const AVClass,
+    last: *const AVOption,
+    unit: Option<Descriptor>,
+}
+
+impl DescriptorIter {
+    fn new(class: *const AVClass, unit: Option<Descriptor>) -> Self {
+        DescriptorIter {
+            class,
+            last: ptr::null(),
+            unit,
+        }
+    }
+}
+
+impl Iterator for DescriptorIter {
+    type Item = Descriptor;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            if self.class.is_null() {
+                return None;
+            }
+
+            loop {
+                // av_opt_next only looks at the AVClass pointer every
+                // options-enabled object starts with.
+                let object = &self.class as *const *const AVClass as *mut c_void;
+                self.last = av_opt_next(object, self.last);
+
+                if self.last.is_null() {
+                    return None;
+                }
+
+                let option = Descriptor::wrap(self.last, self.class);
+
+                match self.unit {
+                    None => return Some(option),
+
+                    Some(ref owner) => {
+                        if option.kind() == Type::Constant
+                            && owner.unit().is_some()
+                            && option.unit() == owner.unit()
+                        {
+                            return Some(option);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct ClassIter {
+    parent: *const AVClass,
+    #[cfg(feature = "ffmpeg_4_4")]
+    opaque: *mut c_void,
+    #[cfg(not(feature = "ffmpeg_4_4"))]
+    last: *const AVClass,
+}
+
+impl ClassIter {
+    fn new(parent: *const AVClass) -> Self {
+        ClassIter {
+            parent,
+            #[cfg(feature = "ffmpeg_4_4")]
+            opaque: ptr::null_mut(),
+            #[cfg(not(feature = "ffmpeg_4_4"))]
+            last: ptr::null(),
+        }
+    }
+}
+
+impl Iterator for ClassIter {
+    type Item = Class;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            #[cfg(feature = "ffmpeg_4_4")]
+            let ptr = av_opt_child_class_iterate(self.parent, &mut self.opaque);
+
+            #[cfg(not(feature = "ffmpeg_4_4"))]
+            let ptr = {
+                self.last = av_opt_child_class_next(self.parent, self.last);
+                self.last
+            };
+
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Class::wrap(ptr))
+            }
+        }
+    }
+}
@@ -1,5 +1,11 @@
 mod traits;
-pub use self::traits::{Gettable, Iterable, Settable, Target};
+pub use self::traits::{ChildIter, Gettable, Iterable, Settable, Target};
+
+pub mod flag;
+pub use self::flag::Flags;
+
+pub mod descriptor;
+pub use self::descriptor::{Class, ClassIter, Descriptor, DescriptorIter, Value};
 
 use crate::ffi::AVOptionType::*;
 use crate::ffi::*;
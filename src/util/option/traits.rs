@@ -1,12 +1,15 @@
 //! NOTE: this will be much better once specialization comes
 
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
 use std::mem;
+use std::ptr;
 
+use super::{Class, DescriptorIter};
 use crate::ffi::*;
 use crate::util::format;
-use crate::{ChannelLayout, Error, Rational};
-use libc::{c_int, c_void};
+use crate::{ChannelLayout, Dictionary, Error, Rational};
+use libc::{c_char, c_int, c_void};
 
 macro_rules! check {
     ($expr:expr) => {
@@ -157,6 +160,213 @@ pub trait Settable: Target {
     }
 }
 
-pub trait Gettable: Target {}
+pub trait Gettable: Target {
+    fn get_str(&self, name: &str) -> Result<String, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = ptr::null_mut();
+
+            check!(av_opt_get(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))?;
+
+            if value.is_null() {
+                return Ok(String::new());
+            }
+
+            let result = CStr::from_ptr(value as *const c_char)
+                .to_string_lossy()
+                .into_owned();
+            av_free(value as *mut c_void);
+
+            Ok(result)
+        }
+    }
+
+    fn get_int(&self, name: &str) -> Result<i64, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = 0;
+
+            check!(av_opt_get_int(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| value)
+        }
+    }
+
+    fn get_double(&self, name: &str) -> Result<f64, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = 0.0;
+
+            check!(av_opt_get_double(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| value)
+        }
+    }
 
-pub trait Iterable: Target {}
+    fn get_rational(&self, name: &str) -> Result<Rational, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVRational { num: 0, den: 1 };
+
+            check!(av_opt_get_q(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| Rational::from(value))
+        }
+    }
+
+    fn get_image_size(&self, name: &str) -> Result<(u32, u32), Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut w = 0;
+            let mut h = 0;
+
+            check!(av_opt_get_image_size(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut w,
+                &mut h
+            ))
+            .map(|_| (w as u32, h as u32))
+        }
+    }
+
+    fn get_pixel_format(&self, name: &str) -> Result<format::Pixel, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVPixelFormat::AV_PIX_FMT_NONE;
+
+            check!(av_opt_get_pixel_fmt(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| format::Pixel::from(value))
+        }
+    }
+
+    fn get_sample_format(&self, name: &str) -> Result<format::Sample, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = AVSampleFormat::AV_SAMPLE_FMT_NONE;
+
+            check!(av_opt_get_sample_fmt(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| format::Sample::from(value))
+        }
+    }
+
+    fn get_channel_layout(&self, name: &str) -> Result<ChannelLayout, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+
+            #[cfg(not(feature = "ffmpeg_7_0"))]
+            {
+                let mut value = 0;
+
+                check!(av_opt_get_channel_layout(
+                    self.as_ptr() as *mut _,
+                    name.as_ptr(),
+                    AV_OPT_SEARCH_CHILDREN,
+                    &mut value
+                ))
+                .map(|_| ChannelLayout::from_bits_truncate(value as _))
+            }
+
+            #[cfg(feature = "ffmpeg_7_0")]
+            {
+                let mut value = mem::zeroed::<AVChannelLayout>();
+
+                check!(av_opt_get_chlayout(
+                    self.as_ptr() as *mut _,
+                    name.as_ptr(),
+                    AV_OPT_SEARCH_CHILDREN,
+                    &mut value
+                ))
+                .map(|_| ChannelLayout::from(value))
+            }
+        }
+    }
+
+    fn get_dictionary(&self, name: &str) -> Result<Dictionary<'static>, Error> {
+        unsafe {
+            let name = CString::new(name).unwrap();
+            let mut value = ptr::null_mut();
+
+            check!(av_opt_get_dict_val(
+                self.as_ptr() as *mut _,
+                name.as_ptr(),
+                AV_OPT_SEARCH_CHILDREN,
+                &mut value
+            ))
+            .map(|_| Dictionary::own(value))
+        }
+    }
+}
+
+pub trait Iterable: Target {
+    /// The class describing this object's own options.
+    fn class(&self) -> Class {
+        unsafe { Class::wrap(*(self.as_ptr() as *const *const AVClass)) }
+    }
+
+    /// This object's own options, not including those of its children.
+    fn options(&self) -> DescriptorIter {
+        self.class().options()
+    }
+
+    /// The classes of the child objects this object currently has, e.g. the
+    /// codec's private options once an `AVCodecContext` has been allocated
+    /// for a specific codec.
+    fn children(&self) -> ChildIter<'_> {
+        ChildIter {
+            parent: self.as_ptr() as *mut c_void,
+            last: ptr::null_mut(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub struct ChildIter<'a> {
+    parent: *mut c_void,
+    last: *mut c_void,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl Iterator for ChildIter<'_> {
+    type Item = Class;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        unsafe {
+            self.last = av_opt_child_next(self.parent, self.last);
+
+            if self.last.is_null() {
+                None
+            } else {
+                Some(Class::wrap(*(self.last as *const *const AVClass)))
+            }
+        }
+    }
+}
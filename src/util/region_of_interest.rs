@@ -0,0 +1,170 @@
+use std::mem;
+use std::ptr;
+
+use crate::ffi::*;
+use crate::frame::{self, Frame, side_data};
+use crate::{Error, Rational};
+
+/// A rectangle of a frame and how much to change its quality by, in the
+/// `AVRegionOfInterest` layout encoders read.
+///
+/// Edges are in pixels from the top left corner of the frame, with `right`
+/// and `bottom` exclusive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub top: i32,
+    pub bottom: i32,
+    pub left: i32,
+    pub right: i32,
+    /// Quantizer offset within `[-1, 1]`: negative values raise the quality
+    /// of the region and positive ones lower it, relative to the range of
+    /// quantizers of the encoder.
+    pub qoffset: Rational,
+}
+
+impl Region {
+    pub fn new<R: Into<Rational>>(
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+        qoffset: R,
+    ) -> Self {
+        Region {
+            top,
+            bottom,
+            left,
+            right,
+            qoffset: qoffset.into(),
+        }
+    }
+
+    fn is_valid(&self, width: u32, height: u32) -> bool {
+        let qoffset = self.qoffset;
+
+        0 <= self.left
+            && self.left < self.right
+            && self.right as u32 <= width
+            && 0 <= self.top
+            && self.top < self.bottom
+            && self.bottom as u32 <= height
+            && qoffset.denominator() != 0
+            && qoffset.numerator().unsigned_abs() <= qoffset.denominator().unsigned_abs()
+    }
+}
+
+/// The regions of interest of a frame, carried as `REGIONS_OF_INTEREST` side
+/// data and honored by encoders such as libx264, libx265 and libvpx.
+///
+/// Regions are listed by importance: where they overlap, encoders use the
+/// first one.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct RegionsOfInterest {
+    regions: Vec<Region>,
+}
+
+impl RegionsOfInterest {
+    pub fn new() -> Self {
+        RegionsOfInterest::default()
+    }
+
+    /// Adds a region, less important than those already added.
+    pub fn add(&mut self, region: Region) -> &mut Self {
+        self.regions.push(region);
+        self
+    }
+
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn from_frame(frame: &Frame) -> Option<Self> {
+        let side_data = frame.side_data(side_data::Type::REGIONS_OF_INTEREST)?;
+        let data = side_data.data();
+
+        if data.len() < mem::size_of::<AVRegionOfInterest>() {
+            return None;
+        }
+
+        // Every entry records the size of the structure, which may grow.
+        let first = unsafe { ptr::read_unaligned(data.as_ptr() as *const AVRegionOfInterest) };
+        let stride = first.self_size as usize;
+        if stride < mem::size_of::<AVRegionOfInterest>() {
+            return None;
+        }
+
+        let regions = data
+            .chunks_exact(stride)
+            .map(|entry| {
+                let raw =
+                    unsafe { ptr::read_unaligned(entry.as_ptr() as *const AVRegionOfInterest) };
+
+                Region {
+                    top: raw.top,
+                    bottom: raw.bottom,
+                    left: raw.left,
+                    right: raw.right,
+                    qoffset: raw.qoffset.into(),
+                }
+            })
+            .collect();
+
+        Some(RegionsOfInterest { regions })
+    }
+
+    /// Sets these as the frame's `REGIONS_OF_INTEREST` side data, for the
+    /// encoder it is sent to next, or removes it if there are no regions.
+    ///
+    /// Fails with `EINVAL` if a region is empty, does not fit in the frame
+    /// or has a quantizer offset outside of `[-1, 1]`, leaving the frame
+    /// untouched.
+    pub fn add_to_frame(&self, frame: &mut frame::Video) -> Result<(), Error> {
+        let (width, height) = (frame.width(), frame.height());
+
+        if !self
+            .regions
+            .iter()
+            .all(|region| region.is_valid(width, height))
+        {
+            return Err(Error::Other {
+                errno: libc::EINVAL,
+            });
+        }
+
+        frame.remove_side_data(side_data::Type::REGIONS_OF_INTEREST);
+
+        if self.regions.is_empty() {
+            return Ok(());
+        }
+
+        let size = mem::size_of::<AVRegionOfInterest>();
+        let mut side_data = frame
+            .new_side_data(
+                side_data::Type::REGIONS_OF_INTEREST,
+                size * self.regions.len(),
+            )
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            let data = (*side_data.as_mut_ptr()).data as *mut AVRegionOfInterest;
+
+            for (index, region) in self.regions.iter().enumerate() {
+                ptr::write_unaligned(
+                    data.add(index),
+                    AVRegionOfInterest {
+                        self_size: size as u32,
+                        top: region.top,
+                        bottom: region.bottom,
+                        left: region.left,
+                        right: region.right,
+                        qoffset: region.qoffset.into(),
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use std::ptr;
+use std::slice;
+
+use crate::Error;
+use crate::frame::{Frame, SideData, side_data};
+
+/// A "user data unregistered" SEI message of H.264, H.265 or H.266: a UUID
+/// identifying the format of the payload, then the payload.
+///
+/// Decoders attach each message they find to the frame as `SEI_UNREGISTERED`
+/// side data, and encoders with the `udu_sei` option set (see [`enable`])
+/// write those of the frames they are sent to the bitstream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeiUnregistered {
+    pub uuid: [u8; 16],
+    pub payload: Vec<u8>,
+}
+
+impl SeiUnregistered {
+    pub fn new<P: Into<Vec<u8>>>(uuid: [u8; 16], payload: P) -> Self {
+        SeiUnregistered {
+            uuid,
+            payload: payload.into(),
+        }
+    }
+
+    /// Decodes the payload of side data of type `SEI_UNREGISTERED`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < 16 {
+            return None;
+        }
+
+        let (uuid, payload) = data.split_at(16);
+
+        Some(SeiUnregistered {
+            uuid: uuid.try_into().unwrap(),
+            payload: payload.to_vec(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.uuid[..], &self.payload].concat()
+    }
+
+    /// Every message attached to `frame`, in order.
+    pub fn from_frame(frame: &Frame) -> Vec<Self> {
+        unsafe {
+            let ptr = frame.as_ptr();
+            if (*ptr).nb_side_data == 0 {
+                return Vec::new();
+            }
+
+            slice::from_raw_parts((*ptr).side_data, (*ptr).nb_side_data as usize)
+                .iter()
+                .map(|&side_data| SideData::wrap(side_data))
+                .filter(|side_data| side_data.kind() == side_data::Type::SEI_UNREGISTERED)
+                .filter_map(|side_data| Self::from_bytes(side_data.data()))
+                .collect()
+        }
+    }
+
+    /// Attaches the message to `frame`. Unlike other side data, messages
+    /// accumulate; remove the frame's `SEI_UNREGISTERED` side data first to
+    /// replace them.
+    pub fn add_to_frame(&self, frame: &mut Frame) -> Result<(), Error> {
+        let bytes = self.to_bytes();
+        let mut side_data = frame
+            .new_side_data(side_data::Type::SEI_UNREGISTERED, bytes.len())
+            .ok_or(Error::Other {
+                errno: libc::ENOMEM,
+            })?;
+
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), (*side_data.as_mut_ptr()).data, bytes.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// Sets the `udu_sei` option of an encoder, before opening it, so that it
+/// writes the `SEI_UNREGISTERED` side data of frames to the bitstream.
+///
+/// Fails with `OptionNotFound` for encoders without support, which include
+/// the native ones; libx264 and libx265 have it.
+#[cfg(feature = "codec")]
+pub fn enable(encoder: &mut crate::codec::Context) -> Result<(), Error> {
+    use crate::util::option::Settable;
+
+    encoder.set_int("udu_sei", 1)
+}
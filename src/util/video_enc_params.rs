@@ -0,0 +1,158 @@
+use std::mem;
+use std::ptr;
+
+use libc::{c_int, c_uint};
+
+use crate::frame::{Frame, side_data};
+
+// libavutil/video_enc_params.h, which ffmpeg-sys does not bind.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVVideoEncParams {
+    nb_blocks: c_uint,
+    blocks_offset: usize,
+    block_size: usize,
+    type_: c_int,
+    qp: i32,
+    delta_qp: [[i32; 2]; 4],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct AVVideoBlockParams {
+    src_x: c_int,
+    src_y: c_int,
+    w: c_int,
+    h: c_int,
+    delta_qp: i32,
+}
+
+/// The codec the quantizers are expressed for, which sets their scale.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Type {
+    None,
+    VP9,
+    H264,
+    MPEG2,
+}
+
+impl From<c_int> for Type {
+    fn from(value: c_int) -> Type {
+        match value {
+            0 => Type::VP9,
+            1 => Type::H264,
+            2 => Type::MPEG2,
+            _ => Type::None,
+        }
+    }
+}
+
+/// The quantizers a decoder used for a frame, from `VIDEO_ENC_PARAMS` frame
+/// side data.
+///
+/// Decoders export these with `decoder::ExportData::VIDEO_ENC_PARAMS` set.
+#[derive(Clone)]
+pub struct VideoEncParams<'a> {
+    raw: AVVideoEncParams,
+    data: &'a [u8],
+}
+
+impl<'a> VideoEncParams<'a> {
+    /// Decodes the payload of side data of type `VIDEO_ENC_PARAMS`.
+    pub fn from_bytes(data: &'a [u8]) -> Option<Self> {
+        if data.len() < mem::size_of::<AVVideoEncParams>() {
+            return None;
+        }
+
+        let raw = unsafe { ptr::read_unaligned(data.as_ptr() as *const AVVideoEncParams) };
+        let end = (raw.nb_blocks as usize)
+            .checked_mul(raw.block_size)
+            .and_then(|size| size.checked_add(raw.blocks_offset));
+
+        if raw.nb_blocks > 0 && raw.block_size < mem::size_of::<AVVideoBlockParams>() {
+            return None;
+        }
+        if !end.is_some_and(|end| end <= data.len()) {
+            return None;
+        }
+
+        Some(VideoEncParams { raw, data })
+    }
+
+    pub fn from_frame(frame: &'a Frame) -> Option<Self> {
+        Self::from_bytes(frame.side_data(side_data::Type::VIDEO_ENC_PARAMS)?.data())
+    }
+
+    pub fn kind(&self) -> Type {
+        Type::from(self.raw.type_)
+    }
+
+    /// The base quantizer of the frame.
+    pub fn qp(&self) -> i32 {
+        self.raw.qp
+    }
+
+    /// Offsets to the base quantizer, per plane and then for the AC and DC
+    /// coefficients.
+    pub fn delta_qp(&self) -> [[i32; 2]; 4] {
+        self.raw.delta_qp
+    }
+
+    /// The blocks the frame is split into, for codecs with per-block
+    /// quantizers. Blocks may overlap or leave parts of the frame out.
+    pub fn blocks(&self) -> Blocks<'a> {
+        Blocks {
+            params: self.clone(),
+            current: 0,
+        }
+    }
+}
+
+/// A block of a frame and the quantizer used for it.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Block {
+    /// Position of the top left corner, in luma pixels.
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    /// The quantizer of the block, with its offset to the frame's applied.
+    pub qp: i32,
+}
+
+pub struct Blocks<'a> {
+    params: VideoEncParams<'a>,
+    current: usize,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.current >= self.params.raw.nb_blocks as usize {
+            return None;
+        }
+
+        let offset = self.params.raw.blocks_offset + self.current * self.params.raw.block_size;
+        let raw = unsafe {
+            ptr::read_unaligned(self.params.data[offset..].as_ptr() as *const AVVideoBlockParams)
+        };
+        self.current += 1;
+
+        Some(Block {
+            x: raw.src_x,
+            y: raw.src_y,
+            width: raw.w,
+            height: raw.h,
+            qp: self.params.raw.qp + raw.delta_qp,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.params.raw.nb_blocks as usize - self.current;
+
+        (length, Some(length))
+    }
+}
+
+impl<'a> ExactSizeIterator for Blocks<'a> {}
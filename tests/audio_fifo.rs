@@ -0,0 +1,92 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::util::audio_fifo::AudioFifo;
+use crate::ffmpeg::{
+    ChannelLayout, Error,
+    format::{Sample, sample::Type},
+    frame,
+};
+
+const RATE: u32 = 48_000;
+
+fn input(start: usize, samples: usize) -> frame::Audio {
+    let mut frame = frame::Audio::new(Sample::I16(Type::Planar), samples, ChannelLayout::STEREO);
+    frame.set_rate(RATE);
+    frame.set_pts(Some(start as i64));
+
+    for (i, sample) in frame.plane_mut::<i16>(0).iter_mut().enumerate() {
+        *sample = (start + i) as i16;
+    }
+    for (i, sample) in frame.plane_mut::<i16>(1).iter_mut().enumerate() {
+        *sample = -((start + i) as i16);
+    }
+
+    frame
+}
+
+#[test]
+fn reblocks_to_fixed_frame_size() {
+    let mut fifo = AudioFifo::new(Sample::I16(Type::Planar), ChannelLayout::STEREO, RATE).unwrap();
+
+    for start in (0..2100).step_by(700) {
+        fifo.write(&input(start, 700)).unwrap();
+    }
+    assert_eq!(fifo.samples(), 2100);
+
+    let mut output = Vec::new();
+    while let Some(frame) = fifo.read(1024) {
+        output.push(frame);
+    }
+    assert!(fifo.read(1024).is_none());
+
+    let last = fifo.flush(1024).unwrap();
+    assert!(fifo.flush(1024).is_none());
+    assert!(fifo.is_empty());
+    output.push(last);
+
+    let sizes: Vec<_> = output.iter().map(|f| f.samples()).collect();
+    assert_eq!(sizes, [1024, 1024, 52]);
+
+    let pts: Vec<_> = output.iter().map(|f| f.pts()).collect();
+    assert_eq!(pts, [Some(0), Some(1024), Some(2048)]);
+
+    // Samples come out in order and planes stay separate.
+    let left: Vec<i16> = output
+        .iter()
+        .flat_map(|f| f.plane::<i16>(0).to_vec())
+        .collect();
+    let right: Vec<i16> = output
+        .iter()
+        .flat_map(|f| f.plane::<i16>(1).to_vec())
+        .collect();
+    assert!(left.iter().enumerate().all(|(i, &s)| s == i as i16));
+    assert!(right.iter().enumerate().all(|(i, &s)| s == -(i as i16)));
+}
+
+#[test]
+fn advances_pts_in_time_base() {
+    let mut fifo = AudioFifo::new(Sample::I16(Type::Planar), ChannelLayout::STEREO, RATE).unwrap();
+    fifo.set_time_base((1, 90_000));
+
+    let mut frame = input(0, 4800);
+    frame.set_pts(Some(90_000));
+    fifo.write(&frame).unwrap();
+
+    let pts: Vec<_> = std::iter::from_fn(|| fifo.read(960))
+        .map(|f| f.pts().unwrap())
+        .collect();
+    assert_eq!(pts, [90_000, 91_800, 93_600, 95_400, 97_200]);
+}
+
+#[test]
+fn rejects_mismatched_frames() {
+    let mut fifo = AudioFifo::new(Sample::I16(Type::Planar), ChannelLayout::MONO, RATE).unwrap();
+
+    assert_eq!(fifo.write(&input(0, 16)), Err(Error::InvalidData));
+    assert!(fifo.is_empty());
+}
+
+#[test]
+fn rejects_formats_without_samples() {
+    assert!(AudioFifo::new(Sample::None, ChannelLayout::STEREO, RATE).is_err());
+}
@@ -0,0 +1,49 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::codec::{self, bsf};
+use crate::ffmpeg::{Error, Packet, media};
+
+#[test]
+fn lookup_by_name() {
+    let filter = bsf::find("h264_mp4toannexb").expect("h264_mp4toannexb is built in");
+    assert_eq!(filter.name(), "h264_mp4toannexb");
+    assert!(filter.supports(codec::Id::H264));
+    assert!(!filter.supports(codec::Id::AAC));
+
+    assert!(bsf::find("no_such_bsf").is_none());
+    assert!(matches!(
+        bsf::Context::by_name("no_such_bsf"),
+        Err(Error::BsfNotFound)
+    ));
+}
+
+#[test]
+fn null_filter_passes_packets_through() {
+    let mut parameters = codec::Parameters::new();
+    parameters.set_medium(media::Type::Data);
+    parameters.set_id(codec::Id::BIN_DATA);
+
+    let mut bsf = bsf::Context::by_name("null").unwrap();
+    bsf.set_parameters(parameters).unwrap();
+    bsf.set_time_base((1, 90_000));
+    bsf.init().unwrap();
+
+    assert_eq!(bsf.parameters().unwrap().id(), codec::Id::BIN_DATA);
+    assert_eq!(bsf.time_base(), (1, 90_000).into());
+
+    let mut input = Packet::copy(&[1, 2, 3, 4]);
+    input.set_pts(Some(42));
+    input.set_dts(Some(42));
+    bsf.send_packet(&input).unwrap();
+
+    // The filter took its own reference; the caller's packet is intact.
+    assert_eq!(input.data(), Some(&[1u8, 2, 3, 4][..]));
+
+    let mut output = Packet::empty();
+    bsf.receive_packet(&mut output).unwrap();
+    assert_eq!(output.data(), Some(&[1u8, 2, 3, 4][..]));
+    assert_eq!(output.pts(), Some(42));
+
+    bsf.send_eof().unwrap();
+    assert_eq!(bsf.receive_packet(&mut output), Err(Error::Eof));
+}
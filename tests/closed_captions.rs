@@ -0,0 +1,105 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::closed_captions::{ClosedCaptions, Triplet, Type};
+use crate::ffmpeg::subtitle::Rect;
+use crate::ffmpeg::{Subtitle, codec, decoder, format, frame};
+
+/// Sets the odd parity bit CEA-608 requires on each byte.
+fn parity(pair: [u8; 2]) -> [u8; 2] {
+    pair.map(|byte| {
+        if byte.count_ones() % 2 == 0 {
+            byte | 0x80
+        } else {
+            byte
+        }
+    })
+}
+
+/// "HI" as a pop-on caption on CC1, then displayed and erased.
+fn captions() -> ClosedCaptions {
+    ClosedCaptions::new(
+        [[0x14, 0x20], [b'H', b'I'], [0x14, 0x2f], [0x14, 0x2c]]
+            .into_iter()
+            .map(|pair| Triplet::new(Type::Cea608Field1, parity(pair)))
+            .collect(),
+    )
+}
+
+#[test]
+fn triplets_round_trip() {
+    let triplet = Triplet::new(Type::DtvccStart, [0x02, 0x21]);
+    assert_eq!(triplet.to_bytes(), [0xff, 0x02, 0x21]);
+    assert_eq!(Triplet::from_bytes(triplet.to_bytes()), triplet);
+
+    let padding = Triplet::from_bytes([0xfa, 0x00, 0x00]);
+    assert!(!padding.valid);
+    assert_eq!(padding.kind, Type::DtvccData);
+
+    let mut captions = captions();
+    captions.triplets.push(padding);
+    assert_eq!(ClosedCaptions::from_bytes(&captions.to_bytes()), captions);
+    assert_eq!(captions.data(Type::Cea608Field1).count(), 4);
+    assert_eq!(captions.data(Type::DtvccData).count(), 0);
+
+    // A trailing partial construct is dropped.
+    let mut bytes = captions.to_bytes();
+    bytes.push(0xfc);
+    assert_eq!(ClosedCaptions::from_bytes(&bytes), captions);
+}
+
+#[test]
+fn passthrough_on_frames() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    assert_eq!(ClosedCaptions::from_frame(&frame), None);
+
+    captions().add_to_frame(&mut frame).unwrap();
+    assert_eq!(ClosedCaptions::from_frame(&frame), Some(captions()));
+
+    let mut copy = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    ClosedCaptions::from_frame(&frame)
+        .unwrap()
+        .add_to_frame(&mut copy)
+        .unwrap();
+    assert_eq!(ClosedCaptions::from_frame(&copy), Some(captions()));
+
+    ClosedCaptions::default().add_to_frame(&mut copy).unwrap();
+    assert_eq!(ClosedCaptions::from_frame(&copy), None);
+}
+
+#[test]
+fn decodes_to_ass_text() {
+    let codec = decoder::find(codec::Id::EIA_608).expect("cc_dec is built in");
+    let mut context = codec::Context::new_with_codec(codec).decoder();
+    context.set_packet_time_base((1, 90000));
+    let mut decoder = context.subtitle().unwrap();
+
+    let packet = captions().to_packet(Some(0));
+    assert_eq!(packet.pts(), Some(0));
+    assert_eq!(packet.data(), Some(&captions().to_bytes()[..]));
+
+    // The caption is only emitted once it leaves the screen, which depending
+    // on the FFmpeg version happens within the first packet or on a later
+    // erase.
+    let erase = ClosedCaptions::new(vec![Triplet::new(Type::Cea608Field1, parity([0x14, 0x2c]))]);
+    let mut text = String::new();
+
+    for packet in [
+        packet,
+        erase.to_packet(Some(90000)),
+        erase.to_packet(Some(180000)),
+    ] {
+        let mut subtitle = Subtitle::new();
+
+        if decoder.decode(&packet, &mut subtitle).unwrap() {
+            for rect in subtitle.rects() {
+                match rect {
+                    Rect::Ass(ass) => text.push_str(ass.get()),
+                    Rect::Text(plain) => text.push_str(plain.get()),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    assert!(text.contains("HI"), "decoded {text:?}");
+}
@@ -0,0 +1,126 @@
+// Every test binary only uses some of the fixtures.
+#![allow(dead_code)]
+
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use crate::ffmpeg::format::context::{Input, Output, StreamIo};
+use crate::ffmpeg::{Packet, Rational, codec, encoder, format, frame};
+
+/// An in-memory output, and the buffer it ends up in once dropped.
+pub fn output(name: &str) -> (Output, Arc<Mutex<Vec<u8>>>) {
+    let file = Arc::new(Mutex::new(Vec::new()));
+    let sink = file.clone();
+
+    let output = format::output_with_io(
+        name,
+        None,
+        |_| Ok(Cursor::new(Vec::new())),
+        move |_, cursor: Cursor<Vec<u8>>| *sink.lock().unwrap() = cursor.into_inner(),
+    )
+    .unwrap();
+
+    (output, file)
+}
+
+/// Opens what a dropped [`output`] wrote.
+pub fn input(file: Arc<Mutex<Vec<u8>>>) -> Input {
+    let data = Arc::try_unwrap(file).unwrap().into_inner().unwrap();
+    let io = StreamIo::from_read_seek(Cursor::new(data)).unwrap();
+
+    format::input_from_stream(io, None, None).unwrap()
+}
+
+/// An MPEG-1 encoder at 25 fps with a keyframe every `gop` frames.
+pub fn mpeg1(width: u32, height: u32, gop: u32, b_frames: usize) -> encoder::Video {
+    let codec = encoder::find(codec::Id::MPEG1VIDEO).expect("mpeg1video encoder is built in");
+    let mut video = codec::Context::new_with_codec(codec)
+        .encoder()
+        .video()
+        .unwrap();
+    video.set_width(width);
+    video.set_height(height);
+    video.set_format(format::Pixel::YUV420P);
+    video.set_time_base(Rational(1, 25));
+    video.set_frame_rate(Some(Rational(25, 1)));
+    video.set_gop(gop);
+    video.set_max_b_frames(b_frames);
+
+    video.open().unwrap()
+}
+
+/// Frame `i` of the fixtures is a flat picture of luma `i * 5`.
+pub fn luma(i: i64) -> u8 {
+    (i * 5) as u8
+}
+
+/// A flat YUV 4:2:0 picture of the given luma.
+pub fn flat(width: u32, height: u32, luma: u8) -> frame::Video {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, width, height);
+    frame.data_mut(0).fill(luma);
+    frame.data_mut(1).fill(128);
+    frame.data_mut(2).fill(128);
+
+    frame
+}
+
+/// Encodes `frames`, numbering them from 0, and drains the encoder.
+pub fn encode<I>(encoder: &mut encoder::Video, frames: I) -> Vec<Packet>
+where
+    I: IntoIterator<Item = frame::Video>,
+{
+    let mut packets = Vec::new();
+    let mut drain = |encoder: &mut encoder::Video| {
+        let mut packet = Packet::empty();
+        while encoder.receive_packet(&mut packet).is_ok() {
+            packets.push(packet.clone());
+        }
+    };
+
+    for (i, mut frame) in frames.into_iter().enumerate() {
+        frame.set_pts(Some(i as i64));
+        encoder.send_frame(&frame).unwrap();
+        drain(encoder);
+    }
+    encoder.send_eof().unwrap();
+    drain(encoder);
+
+    packets
+}
+
+/// Muxes `packets`, timed in `time_base`, as the only stream of an
+/// in-memory file in the container `name` implies, and opens it.
+pub fn mux<P>(name: &str, parameters: P, time_base: Rational, packets: &[Packet]) -> Input
+where
+    P: Into<codec::Parameters>,
+{
+    let parameters = parameters.into();
+    let (mut output, file) = output(name);
+
+    let mut stream = output.add_stream(parameters.id()).unwrap();
+    stream.set_parameters(parameters);
+    stream.set_time_base(time_base);
+    output.write_header().unwrap();
+    let stream_time_base = output.stream(0).unwrap().time_base();
+
+    for packet in packets {
+        let mut packet = packet.clone();
+        packet.set_stream(0);
+        packet.rescale_ts(time_base, stream_time_base);
+        packet.write_interleaved(&mut output).unwrap();
+    }
+    output.write_trailer().unwrap();
+    drop(output);
+
+    input(file)
+}
+
+/// `frames` frames of MPEG-1 at 25 fps, `width` by 48 pixels, with a
+/// keyframe every 10 frames, so every 400 ms, in the container `name`
+/// implies; frame `i` has luma [`luma(i)`](luma).
+pub fn source(name: &str, frames: i64, width: u32) -> Input {
+    let mut encoder = mpeg1(width, 48, 10, 0);
+    let packets = encode(&mut encoder, (0..frames).map(|i| flat(width, 48, luma(i))));
+
+    mux(name, &encoder, Rational(1, 25), &packets)
+}
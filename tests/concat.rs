@@ -0,0 +1,51 @@
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use std::time::Duration;
+
+use crate::common::{input, output, source};
+use crate::ffmpeg::format::remux::{self, ConcatError, Mismatch};
+use crate::ffmpeg::{Rational, Rescale};
+
+#[test]
+fn concat_keeps_timestamps_continuous() {
+    // Matroska counts in milliseconds and NUT in frames.
+    let mut inputs = [source("first.mkv", 20, 64), source("second.nut", 30, 64)];
+    let (mut output, file) = output("joined.mkv");
+
+    let offsets = remux::concat(&mut inputs, &mut output).unwrap();
+    drop(output);
+
+    assert_eq!(offsets, [Duration::ZERO, Duration::from_millis(800)]);
+
+    let mut joined = input(file);
+    let time_base = joined.stream(0).unwrap().time_base();
+    let frames: Vec<i64> = joined
+        .packets()
+        .map(|(_, packet)| packet.pts().unwrap().rescale(time_base, Rational(1, 25)))
+        .collect();
+
+    assert_eq!(frames, (0..50).collect::<Vec<_>>());
+}
+
+#[test]
+fn concat_rejects_incompatible_inputs() {
+    let (mut output, _) = output("rejected.mkv");
+
+    let mut inputs = [source("a.mkv", 5, 64), source("b.mkv", 5, 80)];
+    assert!(matches!(
+        remux::concat(&mut inputs, &mut output),
+        Err(ConcatError::Incompatible {
+            input: 1,
+            stream: 0,
+            mismatch: Mismatch::Dimensions,
+        })
+    ));
+    assert_eq!(output.nb_streams(), 0);
+
+    assert!(matches!(
+        remux::concat(&mut [], &mut output),
+        Err(ConcatError::Empty)
+    ));
+}
@@ -0,0 +1,89 @@
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use std::time::Duration;
+
+use crate::common::{input, output};
+use crate::ffmpeg::format::remux::{self, Segment};
+use crate::ffmpeg::{Rational, Rescale, format};
+
+/// Two seconds of video with a keyframe every 400 ms.
+fn source() -> format::context::Input {
+    common::source("source.mkv", 50, 64)
+}
+
+/// Timestamps of the packets of `input`, in frames.
+fn frames(mut input: format::context::Input) -> Vec<(i64, bool)> {
+    let time_base = input.stream(0).unwrap().time_base();
+
+    input
+        .packets()
+        .map(|(_, packet)| {
+            let pts = packet.pts().unwrap().rescale(time_base, Rational(1, 25));
+            (pts, packet.is_key())
+        })
+        .collect()
+}
+
+#[test]
+fn cut_snaps_to_keyframes() {
+    let mut source = source();
+    let (mut output, file) = output("cut.mkv");
+
+    let segments = remux::cut(
+        &mut source,
+        &mut output,
+        &[Duration::from_millis(1000)..Duration::from_millis(1500)],
+    )
+    .unwrap();
+    drop(output);
+
+    assert_eq!(
+        segments,
+        [Segment {
+            start: Duration::from_millis(800),
+            end: Duration::from_millis(1600),
+            offset: Duration::ZERO,
+        }]
+    );
+
+    let frames = frames(input(file));
+    assert_eq!(frames.len(), 20);
+    assert_eq!(frames[0], (0, true));
+    assert_eq!(frames[10], (10, true));
+    assert_eq!(frames[19], (19, false));
+}
+
+#[test]
+fn cut_joins_segments() {
+    let mut source = source();
+    let (mut output, file) = output("cuts.mkv");
+
+    let segments = remux::cut(
+        &mut source,
+        &mut output,
+        &[
+            Duration::from_millis(200)..Duration::from_millis(500),
+            Duration::from_millis(1700)..Duration::from_secs(10),
+        ],
+    )
+    .unwrap();
+    drop(output);
+
+    assert_eq!(segments[0].start, Duration::ZERO);
+    assert_eq!(segments[0].end, Duration::from_millis(800));
+    assert_eq!(segments[1].start, Duration::from_millis(1600));
+    assert!(segments[1].end >= Duration::from_millis(1960));
+    assert_eq!(segments[1].offset, Duration::from_millis(800));
+
+    let frames = frames(input(file));
+    assert_eq!(frames.len(), 30);
+    assert!(
+        frames
+            .iter()
+            .enumerate()
+            .all(|(i, &(pts, _))| pts == i as i64)
+    );
+    assert!(frames[20].1);
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "ffmpeg_5_0")]
+
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::detection_bbox::{BoundingBox, BoundingBoxHeader};
+use crate::ffmpeg::{Error, Rational, ffi, filter, format, frame};
+
+fn detections() -> BoundingBoxHeader {
+    let mut face = BoundingBox::new(8, 8, 8, 8, "face", Rational(9, 10));
+    face.classifications = vec![
+        ("smiling".to_owned(), Rational(3, 4)),
+        ("glasses".to_owned(), Rational(1, 5)),
+    ];
+
+    let mut header = BoundingBoxHeader::new("face-detector.onnx");
+    header
+        .add(face)
+        .add(BoundingBox::new(0, 20, 4, 4, "cat", Rational(1, 2)));
+    header
+}
+
+#[test]
+fn boxes_round_trip_through_frames() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 32, 32);
+    assert_eq!(BoundingBoxHeader::from_frame(&frame), None);
+
+    detections().add_to_frame(&mut frame).unwrap();
+    let read = BoundingBoxHeader::from_frame(&frame).unwrap();
+    assert_eq!(read, detections());
+    assert_eq!(read.source, "face-detector.onnx");
+    assert_eq!(
+        read.boxes()
+            .iter()
+            .map(|b| b.label.as_str())
+            .collect::<Vec<_>>(),
+        ["face", "cat"]
+    );
+
+    assert_eq!(BoundingBoxHeader::from_bytes(&[0; 16]), None);
+}
+
+#[test]
+fn unrepresentable_boxes_are_rejected() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 32, 32);
+    let einval = Err(Error::Other {
+        errno: libc::EINVAL,
+    });
+
+    let label = "x".repeat(64);
+    let mut long_label = detections();
+    long_label.add(BoundingBox::new(0, 0, 4, 4, &label, Rational(1, 1)));
+    assert_eq!(long_label.add_to_frame(&mut frame), einval);
+
+    let mut classified = detections();
+    let mut object = BoundingBox::new(0, 0, 4, 4, "object", Rational(1, 1));
+    object.classifications = vec![("x".to_owned(), Rational(1, 1)); 5];
+    classified.add(object);
+    assert_eq!(classified.add_to_frame(&mut frame), einval);
+
+    let mut source = detections();
+    source.source = "a\0b".to_owned();
+    assert_eq!(source.add_to_frame(&mut frame), einval);
+
+    assert_eq!(BoundingBoxHeader::from_frame(&frame), None);
+}
+
+#[test]
+fn drawbox_draws_attached_boxes() {
+    let mut graph = filter::Graph::new();
+    let args = format!(
+        "video_size=32x32:pix_fmt={}:time_base=1/25:pixel_aspect=1/1",
+        ffi::AVPixelFormat::from(format::Pixel::YUV420P) as i32,
+    );
+    graph
+        .add(&filter::find("buffer").unwrap(), "in", &args)
+        .unwrap();
+    graph
+        .add(&filter::find("buffersink").unwrap(), "out", "")
+        .unwrap();
+    graph
+        .output("in", 0)
+        .unwrap()
+        .input("out", 0)
+        .unwrap()
+        .parse("drawbox=box_source=side_data_detection_bboxes:color=white:t=fill")
+        .unwrap();
+    graph.validate().unwrap();
+
+    let mut input = frame::Video::new(format::Pixel::YUV420P, 32, 32);
+    input.data_mut(0).fill(16);
+    input.data_mut(1).fill(128);
+    input.data_mut(2).fill(128);
+    input.set_pts(Some(0));
+    detections().add_to_frame(&mut input).unwrap();
+    graph.get("in").unwrap().source().add(&input).unwrap();
+
+    let mut output = frame::Video::empty();
+    graph.get("out").unwrap().sink().frame(&mut output).unwrap();
+
+    let stride = output.stride(0);
+    assert!(output.data(0)[12 * stride + 12] > 128);
+    assert_eq!(output.data(0)[0], 16);
+    assert_eq!(BoundingBoxHeader::from_frame(&output), Some(detections()));
+}
@@ -0,0 +1,112 @@
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use crate::common::{input, output};
+use crate::ffmpeg::display::DisplayMatrix;
+use crate::ffmpeg::{Packet, Rational, codec, encoder, format, frame};
+
+#[test]
+fn rotation_and_flip() {
+    let identity = DisplayMatrix::default();
+    assert_eq!(
+        identity.values(),
+        [1 << 16, 0, 0, 0, 1 << 16, 0, 0, 0, 1 << 30]
+    );
+    assert_eq!(identity.rotation(), Some(0.0));
+    assert!(!identity.is_flipped());
+
+    let mut matrix = DisplayMatrix::from_rotation(90.0);
+    assert_eq!(matrix.rotation(), Some(90.0));
+    assert!(!matrix.is_flipped());
+
+    matrix.flip(true, false);
+    assert!(matrix.is_flipped());
+    matrix.flip(true, false);
+    assert_eq!(matrix, DisplayMatrix::from_rotation(90.0));
+
+    // Flipping both ways is a half turn.
+    let mut matrix = DisplayMatrix::default();
+    matrix.flip(true, true);
+    assert!(!matrix.is_flipped());
+    assert_eq!(matrix.rotation().map(f64::abs), Some(180.0));
+
+    assert_eq!(DisplayMatrix::new([0; 9]).rotation(), None);
+}
+
+#[test]
+fn round_trips_through_bytes_and_frames() {
+    let matrix = DisplayMatrix::from_rotation(-90.0);
+    assert_eq!(DisplayMatrix::from_bytes(&matrix.to_bytes()), Some(matrix));
+    assert_eq!(DisplayMatrix::from_bytes(&[0; 8]), None);
+
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    assert_eq!(DisplayMatrix::from_frame(&frame), None);
+
+    matrix.add_to_frame(&mut frame).unwrap();
+    DisplayMatrix::from_rotation(180.0)
+        .add_to_frame(&mut frame)
+        .unwrap();
+    assert_eq!(
+        DisplayMatrix::from_frame(&frame),
+        Some(DisplayMatrix::from_rotation(180.0))
+    );
+}
+
+#[test]
+fn written_to_mp4_track_header() {
+    let (mut output, file) = output("rotated.mp4");
+    let global_header = output
+        .format()
+        .flags()
+        .contains(format::Flags::GLOBAL_HEADER);
+
+    let codec = encoder::find(codec::Id::MPEG4).expect("mpeg4 encoder is built in");
+    let mut video = codec::Context::new_with_codec(codec)
+        .encoder()
+        .video()
+        .unwrap();
+    video.set_width(64);
+    video.set_height(48);
+    video.set_format(format::Pixel::YUV420P);
+    video.set_time_base(Rational(1, 25));
+    if global_header {
+        video.set_flags(codec::Flags::GLOBAL_HEADER);
+    }
+    let mut encoder = video.open().unwrap();
+
+    let mut stream = output.add_stream(codec::Id::MPEG4).unwrap();
+    stream.set_parameters(&encoder);
+    stream.set_time_base(Rational(1, 25));
+    stream
+        .set_display_matrix(&DisplayMatrix::from_rotation(90.0))
+        .unwrap();
+    assert_eq!(
+        output.stream(0).unwrap().display_matrix(),
+        Some(DisplayMatrix::from_rotation(90.0))
+    );
+
+    output.write_header().unwrap();
+    let time_base = output.stream(0).unwrap().time_base();
+
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 64, 48);
+    frame.set_pts(Some(0));
+    encoder.send_frame(&frame).unwrap();
+    encoder.send_eof().unwrap();
+
+    let mut packet = Packet::empty();
+    while encoder.receive_packet(&mut packet).is_ok() {
+        packet.set_stream(0);
+        packet.rescale_ts(Rational(1, 25), time_base);
+        packet.write_interleaved(&mut output).unwrap();
+    }
+    output.write_trailer().unwrap();
+    drop(output);
+
+    let input = input(file);
+
+    assert_eq!(
+        input.stream(0).unwrap().display_matrix(),
+        Some(DisplayMatrix::from_rotation(90.0))
+    );
+}
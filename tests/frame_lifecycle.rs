@@ -0,0 +1,110 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::format::sample::{Sample, Type};
+use crate::ffmpeg::{ChannelLayout, Dictionary, format, frame};
+
+fn gradient() -> frame::Video {
+    let mut frame = frame::Video::new(format::Pixel::GRAY8, 32, 32);
+    let stride = frame.stride(0);
+
+    for (y, row) in frame.data_mut(0).chunks_mut(stride).enumerate() {
+        for (x, pixel) in row.iter_mut().take(32).enumerate() {
+            *pixel = (y * 8 + x / 4) as u8;
+        }
+    }
+
+    frame
+}
+
+#[test]
+fn shallow_clones_share_until_written() {
+    let mut frame = gradient();
+    frame.set_pts(Some(7));
+    assert!(frame.is_writable());
+
+    let mut copy = frame.shallow_clone().unwrap();
+    assert_eq!(copy.pts(), Some(7));
+    assert_eq!(copy.data(0).as_ptr(), frame.data(0).as_ptr());
+    assert!(!frame.is_writable());
+    assert!(!copy.is_writable());
+
+    copy.make_writable().unwrap();
+    assert_ne!(copy.data(0).as_ptr(), frame.data(0).as_ptr());
+    assert_eq!(copy.data(0), frame.data(0));
+
+    copy.data_mut(0)[0] = 255;
+    assert_eq!(frame.data(0)[0], 0);
+
+    // With the other reference gone, the buffer is ours again.
+    drop(copy);
+    assert!(frame.is_writable());
+    let before = frame.data(0).as_ptr();
+    frame.make_writable().unwrap();
+    assert_eq!(frame.data(0).as_ptr(), before);
+
+    let mut audio = frame::Audio::new(Sample::I16(Type::Planar), 64, ChannelLayout::STEREO);
+    let shared = audio.shallow_clone().unwrap();
+    assert_eq!(shared.samples(), 64);
+    assert_eq!(shared.data(1).as_ptr(), audio.data(1).as_ptr());
+    audio.make_writable().unwrap();
+    assert_ne!(shared.data(1).as_ptr(), audio.data(1).as_ptr());
+}
+
+#[test]
+fn copy_props_carries_timestamps_and_metadata() {
+    let mut source = gradient();
+    source.set_pts(Some(1234));
+    let mut metadata = Dictionary::new();
+    metadata.set("lavfi.scene_score", "0.5");
+    source.set_metadata(metadata);
+
+    let mut converted = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    converted.copy_props(&source).unwrap();
+
+    assert_eq!(converted.pts(), Some(1234));
+    assert_eq!(converted.metadata().get("lavfi.scene_score"), Some("0.5"));
+    assert_eq!(converted.format(), format::Pixel::YUV420P);
+    assert_eq!(converted.width(), 16);
+}
+
+#[test]
+fn cropping_moves_pointers_without_copying() {
+    let mut frame = gradient();
+    assert_eq!(frame.crop(), (0, 0, 0, 0));
+
+    frame.set_crop(2, 6, 4, 12);
+    assert_eq!(frame.crop(), (2, 6, 4, 12));
+    frame.apply_cropping(true).unwrap();
+
+    assert_eq!((frame.width(), frame.height()), (16, 24));
+    assert_eq!(frame.crop(), (0, 0, 0, 0));
+    // Row 2, column 4 of the gradient.
+    assert_eq!(frame.data(0)[0], 2 * 8 + 1);
+
+    let mut frame = gradient();
+    frame.set_crop(0, 0, 0, 33);
+    assert!(frame.apply_cropping(true).is_err());
+}
+
+#[test]
+fn cropped_planes_end_with_the_buffer() {
+    let mut frame = gradient();
+    let stride = frame.stride(0);
+
+    frame.set_crop(0, 0, 30, 0);
+    frame.apply_cropping(true).unwrap();
+    assert_eq!((frame.width(), frame.height()), (2, 32));
+
+    // Rows keep their whole stride, up to the end of the buffer.
+    let end = unsafe {
+        let buf = (*frame.as_ptr()).buf[0];
+        (*buf).data as usize + (*buf).size as usize
+    };
+    let data = frame.data(0);
+    assert_eq!(data.len(), (stride * 32).min(end - data.as_ptr() as usize));
+    assert_eq!(data[stride * 31 + 1], 31 * 8 + 31 / 4);
+
+    let data = frame.data_mut(0);
+    data[stride * 31 + 1] = 0;
+    assert_eq!(frame.plane_view::<u8>(0).row(31), &[31 * 8 + 30 / 4, 0]);
+}
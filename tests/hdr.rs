@@ -0,0 +1,193 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::hdr::{ContentLightLevel, Luminance, MasteringDisplay, Primaries};
+#[cfg(feature = "ffmpeg_4_2")]
+use crate::ffmpeg::hdr::{DynamicHdrPlus, dynamic_hdr_plus::Window};
+use crate::ffmpeg::{Rational, codec, format, frame};
+
+fn bt2020() -> MasteringDisplay {
+    MasteringDisplay {
+        primaries: Some(Primaries {
+            red: (Rational(17, 25), Rational(8, 25)),
+            green: (Rational(53, 200), Rational(69, 100)),
+            blue: (Rational(3, 20), Rational(3, 50)),
+            white_point: (Rational(3127, 10000), Rational(329, 1000)),
+        }),
+        luminance: Some(Luminance {
+            min: Rational(1, 10000),
+            max: Rational(1000, 1),
+        }),
+    }
+}
+
+#[test]
+fn static_metadata_round_trips_through_frames() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P10LE, 16, 16);
+    assert_eq!(MasteringDisplay::from_frame(&frame), None);
+
+    bt2020().add_to_frame(&mut frame).unwrap();
+    let light = ContentLightLevel {
+        max: 1000,
+        max_frame_average: 400,
+    };
+    light.add_to_frame(&mut frame).unwrap();
+
+    assert_eq!(MasteringDisplay::from_frame(&frame), Some(bt2020()));
+    assert_eq!(ContentLightLevel::from_frame(&frame), Some(light));
+
+    // Attaching again replaces rather than duplicates.
+    let partial = MasteringDisplay {
+        primaries: None,
+        ..bt2020()
+    };
+    partial.add_to_frame(&mut frame).unwrap();
+    assert_eq!(MasteringDisplay::from_frame(&frame), Some(partial));
+
+    assert_eq!(MasteringDisplay::from_bytes(&[0; 4]), None);
+}
+
+#[cfg(feature = "ffmpeg_4_2")]
+fn hdr10_plus() -> (DynamicHdrPlus, Window) {
+    let window = Window {
+        maxscl: [Rational(1, 2), Rational(2, 5), Rational(3, 10)],
+        average_maxrgb: Rational(1, 10),
+        distribution_maxrgb: vec![(1, Rational(1, 100)), (99, Rational(9, 20))],
+        fraction_bright_pixels: Rational(0, 1),
+        knee_point: Some((Rational(1, 8), Rational(1, 4))),
+        bezier_curve_anchors: vec![Rational(1, 3), Rational(2, 3)],
+        color_saturation_weight: None,
+    };
+
+    let mut metadata = DynamicHdrPlus::new();
+    metadata.set_targeted_system_display_maximum_luminance(400);
+    metadata.set_windows(std::slice::from_ref(&window));
+
+    (metadata, window)
+}
+
+#[cfg(feature = "ffmpeg_4_2")]
+#[test]
+fn hdr10_plus_round_trips_through_frames() {
+    let (metadata, window) = hdr10_plus();
+    let mut frame = frame::Video::new(format::Pixel::YUV420P10LE, 16, 16);
+    metadata.add_to_frame(&mut frame).unwrap();
+
+    let decoded = DynamicHdrPlus::from_frame(&frame).unwrap();
+    assert_eq!(decoded.country_code(), 0xb5);
+    assert_eq!(decoded.application_version(), 1);
+    assert_eq!(
+        decoded.targeted_system_display_maximum_luminance(),
+        Rational(400, 1)
+    );
+    assert_eq!(decoded.windows(), [window]);
+}
+
+#[cfg(feature = "ffmpeg_5_0")]
+#[test]
+fn hdr10_plus_round_trips_through_streams() {
+    use std::io::Cursor;
+
+    let mut output = format::output_with_io(
+        "hdr10plus.mkv",
+        None,
+        |_| Ok(Cursor::new(Vec::new())),
+        |_, _: Cursor<Vec<u8>>| (),
+    )
+    .unwrap();
+
+    let (metadata, window) = hdr10_plus();
+    let mut stream = output.add_stream(codec::Id::None).unwrap();
+    metadata.add_to_stream(&mut stream).unwrap();
+
+    let stream = output.stream(0).unwrap();
+    let decoded = DynamicHdrPlus::from_stream(&stream).unwrap();
+    assert_eq!(decoded.windows(), [window]);
+}
+
+#[cfg(feature = "ffmpeg_6_0")]
+#[test]
+fn hdr10_plus_round_trips_through_t35() {
+    let (metadata, _) = hdr10_plus();
+    let payload = metadata.to_t35().unwrap();
+
+    let decoded = DynamicHdrPlus::from_t35(&payload).unwrap();
+    assert_eq!(decoded.country_code(), 0xb5);
+    assert_eq!(
+        decoded.targeted_system_display_maximum_luminance(),
+        Rational(400, 1)
+    );
+    assert_eq!(decoded.windows().len(), 1);
+    assert_eq!(decoded.to_t35().unwrap(), payload);
+
+    assert!(DynamicHdrPlus::from_t35(&[]).is_err());
+}
+
+#[test]
+fn static_metadata_round_trips_through_streams() {
+    use std::io::Cursor;
+
+    let mut output = format::output_with_io(
+        "hdr.mkv",
+        None,
+        |_| Ok(Cursor::new(Vec::new())),
+        |_, _: Cursor<Vec<u8>>| (),
+    )
+    .unwrap();
+
+    let mut stream = output.add_stream(codec::Id::None).unwrap();
+    bt2020().add_to_stream(&mut stream).unwrap();
+    let light = ContentLightLevel {
+        max: 1000,
+        max_frame_average: 400,
+    };
+    light.add_to_stream(&mut stream).unwrap();
+
+    // Attaching again replaces rather than duplicates.
+    let partial = MasteringDisplay {
+        luminance: None,
+        ..bt2020()
+    };
+    partial.add_to_stream(&mut stream).unwrap();
+
+    let stream = output.stream(0).unwrap();
+    assert_eq!(MasteringDisplay::from_stream(&stream), Some(partial));
+    assert_eq!(ContentLightLevel::from_stream(&stream), Some(light));
+
+    #[cfg(feature = "ffmpeg_7_0")]
+    assert_eq!(
+        MasteringDisplay::from_parameters(&stream.parameters()),
+        Some(partial)
+    );
+}
+
+#[cfg(feature = "ffmpeg_4_3")]
+#[test]
+fn dovi_config_round_trips_through_streams() {
+    use std::io::Cursor;
+
+    use crate::ffmpeg::hdr::DoviConfig;
+
+    let mut output = format::output_with_io(
+        "dovi.mp4",
+        None,
+        |_| Ok(Cursor::new(Vec::new())),
+        |_, _: Cursor<Vec<u8>>| (),
+    )
+    .unwrap();
+
+    let dovi = DoviConfig {
+        version_major: 1,
+        profile: 8,
+        level: 6,
+        rpu_present: true,
+        bl_present: true,
+        bl_signal_compatibility_id: 1,
+        ..DoviConfig::default()
+    };
+    let mut stream = output.add_stream(codec::Id::None).unwrap();
+    dovi.add_to_stream(&mut stream).unwrap();
+
+    let stream = output.stream(0).unwrap();
+    assert_eq!(DoviConfig::from_stream(&stream), Some(dovi));
+    assert_eq!(MasteringDisplay::from_stream(&stream), None);
+}
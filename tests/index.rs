@@ -0,0 +1,54 @@
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use crate::ffmpeg::{Error, Rational, Rescale, format};
+
+const FRAMES: i64 = 50;
+
+/// `FRAMES` frames at 25 fps with a keyframe every 10 frames, in an
+/// in-memory Matroska file.
+fn open() -> format::context::Input {
+    common::source("index.mkv", FRAMES, 64)
+}
+
+#[test]
+fn scan_index_finds_every_keyframe() {
+    let mut input = open();
+    let entries = input.scan_index(0).unwrap();
+
+    assert_eq!(entries.len(), FRAMES as usize);
+    for (i, entry) in entries.iter().enumerate() {
+        assert_eq!(entry.is_key(), i % 10 == 0, "entry {i}");
+        assert!(entry.size() > 0);
+    }
+
+    let keyframes: Vec<_> = entries.iter().filter(|entry| entry.is_key()).collect();
+    assert_eq!(keyframes.len(), 5);
+    assert!(keyframes.windows(2).all(|pair| {
+        pair[0].timestamp() < pair[1].timestamp() && pair[0].position() < pair[1].position()
+    }));
+
+    assert_eq!(input.scan_index(1), Err(Error::StreamNotFound));
+}
+
+#[test]
+fn index_entries_follow_the_cues() {
+    let input = open();
+    let stream = input.stream(0).unwrap();
+    let time_base = stream.time_base();
+    let entries: Vec<_> = stream.index_entries().collect();
+
+    assert!(!entries.is_empty());
+    assert!(entries.iter().all(|entry| entry.is_key()));
+    assert_eq!(stream.index_entries().len(), entries.len());
+
+    let target = 23i64.rescale(Rational(1, 25), time_base);
+    let before = stream.keyframe_before(target).unwrap();
+    assert!(before.is_key());
+    assert!(before.timestamp().unwrap() <= target);
+
+    if let Some(after) = stream.keyframe_after(target) {
+        assert!(after.timestamp().unwrap() >= target);
+    }
+}
@@ -0,0 +1,95 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use std::ffi::{c_int, c_void};
+use std::ptr;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::ffmpeg::ffi::{AV_LOG_DEBUG, AV_LOG_WARNING, av_log};
+use crate::ffmpeg::{codec, decoder, log};
+
+/// The log callback is process-wide, so every test here that installs one
+/// or relies on what reaches it holds this while it runs.
+static CALLBACK: Mutex<()> = Mutex::new(());
+
+fn exclusive() -> MutexGuard<'static, ()> {
+    CALLBACK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn capture_collects_messages_of_the_scope() {
+    let _guard = exclusive();
+
+    let ((), messages) = log::capture(|| unsafe {
+        av_log(
+            ptr::null_mut(),
+            AV_LOG_WARNING,
+            c"hello %d\n".as_ptr(),
+            42 as c_int,
+        );
+    });
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].level(), log::Level::Warning);
+    assert_eq!(messages[0].item_name(), None);
+    assert_eq!(messages[0].text(), "hello 42\n");
+
+    // Nothing leaks into a later scope.
+    let ((), messages) = log::capture(|| ());
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn capture_reports_item_name_and_honours_level() {
+    let _guard = exclusive();
+
+    let codec = decoder::find(codec::Id::H264).unwrap();
+    let mut context = codec::Context::new_with_codec(codec);
+
+    let ((), messages) = log::capture(|| unsafe {
+        let avcl = context.as_mut_ptr() as *mut c_void;
+
+        av_log(avcl, AV_LOG_WARNING, c"attributed\n".as_ptr());
+        // Below the default level, so dropped.
+        av_log(avcl, AV_LOG_DEBUG, c"filtered\n".as_ptr());
+    });
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].item_name(), Some("h264"));
+    assert_eq!(messages[0].text(), "attributed\n");
+}
+
+#[test]
+fn colored_messages_keep_their_level() {
+    let _guard = exclusive();
+
+    // `AV_LOG_C(1)`, a color packed above the level.
+    let ((), messages) = log::capture(|| unsafe {
+        av_log(
+            ptr::null_mut(),
+            AV_LOG_WARNING | (1 << 8),
+            c"colored\n".as_ptr(),
+        );
+    });
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].level(), log::Level::Warning);
+}
+
+#[test]
+fn panicking_callback_is_contained() {
+    let _guard = exclusive();
+
+    log::set_callback(|_| panic!("callback panicked"));
+
+    let ((), messages) = log::capture(|| unsafe {
+        av_log(
+            ptr::null_mut(),
+            AV_LOG_WARNING,
+            c"still delivered\n".as_ptr(),
+        );
+    });
+
+    log::reset_callback();
+
+    assert_eq!(messages.len(), 1);
+}
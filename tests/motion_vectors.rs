@@ -0,0 +1,97 @@
+#![cfg(feature = "ffmpeg_4_3")]
+
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use crate::common::{encode, flat, mpeg1};
+use crate::ffmpeg::decoder::ExportData;
+use crate::ffmpeg::motion_vector::{MotionVector, MotionVectors};
+use crate::ffmpeg::{Packet, codec, decoder, format, frame, picture};
+
+/// MPEG-1 of a bright square moving 2 pixels right per frame.
+fn packets() -> Vec<Packet> {
+    let frames = (0..5).map(|i| {
+        let mut frame = flat(64, 64, 16);
+        let stride = frame.stride(0);
+        let x = 16 + 2 * i;
+        for row in frame.data_mut(0).chunks_mut(stride).skip(16).take(16) {
+            row[x..x + 16].fill(235);
+        }
+
+        frame
+    });
+
+    encode(&mut mpeg1(64, 64, 10, 0), frames)
+}
+
+/// The picture type and motion vectors of every frame `decoder` outputs.
+fn decode(mut decoder: decoder::Video) -> Vec<(picture::Type, Vec<MotionVector>)> {
+    let mut frames = Vec::new();
+    let mut drain = |decoder: &mut decoder::Video| {
+        let mut frame = frame::Video::empty();
+        while decoder.receive_frame(&mut frame).is_ok() {
+            let vectors: Vec<_> = frame.motion_vectors().collect();
+            frames.push((frame.kind(), vectors));
+        }
+    };
+
+    for packet in packets() {
+        decoder.send_packet(&packet).unwrap();
+        drain(&mut decoder);
+    }
+    decoder.send_eof().unwrap();
+    drain(&mut decoder);
+
+    frames
+}
+
+fn assert_exported(frames: &[(picture::Type, Vec<MotionVector>)]) {
+    assert_eq!(frames.len(), 5);
+    assert_eq!(frames[0].0, picture::Type::I);
+    assert!(frames[0].1.is_empty());
+
+    for (kind, vectors) in &frames[1..] {
+        assert_eq!(*kind, picture::Type::P);
+        assert!(!vectors.is_empty());
+
+        for vector in vectors {
+            assert_eq!(vector.source(), -1);
+            assert_eq!(vector.size(), (16, 16));
+            assert!(vector.scale() > 0);
+        }
+
+        // The square came from the left.
+        assert!(vectors.iter().any(|vector| vector.motion().0 < 0));
+    }
+}
+
+#[test]
+fn decoder_exports_motion_vectors() {
+    let codec = decoder::find(codec::Id::MPEG1VIDEO).expect("mpeg1video decoder is built in");
+    let mut context = codec::Context::new_with_codec(codec).decoder();
+    assert_eq!(context.export_side_data(), ExportData::empty());
+    context.set_export_side_data(ExportData::MVS);
+
+    assert_exported(&decode(context.video().unwrap()));
+}
+
+#[test]
+fn decoder_exports_motion_vectors_through_flags2() {
+    let codec = decoder::find(codec::Id::MPEG1VIDEO).expect("mpeg1video decoder is built in");
+    let mut context = codec::Context::new_with_codec(codec).decoder();
+    assert_eq!(context.flags2(), codec::Flags2::empty());
+    context.set_flags2(codec::Flags2::EXPORT_MVS);
+
+    assert_exported(&decode(context.video().unwrap()));
+}
+
+#[test]
+fn parses_raw_side_data() {
+    assert_eq!(MotionVectors::new(&[]).len(), 0);
+    // A truncated trailing entry is ignored.
+    assert_eq!(MotionVectors::new(&[0; 7]).len(), 0);
+
+    let frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    assert_eq!(frame.motion_vectors().count(), 0);
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "ffmpeg_5_0")]
+
+extern crate ffmpeg_next as ffmpeg;
+
+use std::sync::Arc;
+
+use crate::ffmpeg::{Opaque, Packet, format, frame};
+
+#[test]
+fn frames_and_packets_hold_opaque() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    assert!(frame.opaque().is_none());
+
+    let value: Opaque = Arc::new(42_u64);
+    frame.set_opaque(Some(value.clone())).unwrap();
+    assert_eq!(frame.opaque().unwrap().downcast_ref::<u64>(), Some(&42));
+
+    // Copies share the value.
+    let copy = frame.clone();
+    assert!(Arc::ptr_eq(&copy.opaque().unwrap(), &value));
+
+    frame.set_opaque(None).unwrap();
+    assert!(frame.opaque().is_none());
+    drop(copy);
+    assert_eq!(Arc::strong_count(&value), 1);
+
+    let mut packet = Packet::copy(&[0; 4]);
+    assert!(packet.opaque().is_none());
+    packet.set_opaque(Some(Arc::new("request-7"))).unwrap();
+    assert_eq!(
+        packet.clone().opaque().unwrap().downcast_ref::<&str>(),
+        Some(&"request-7")
+    );
+}
+
+#[cfg(feature = "ffmpeg_6_0")]
+#[test]
+fn codecs_carry_opaque_through_reordering() {
+    use crate::ffmpeg::{Rational, codec, decoder, encoder};
+
+    let codec = encoder::find(codec::Id::MPEG1VIDEO).expect("mpeg1video encoder is built in");
+    let mut video = codec::Context::new_with_codec(codec)
+        .encoder()
+        .video()
+        .unwrap();
+    video.set_width(64);
+    video.set_height(48);
+    video.set_format(format::Pixel::YUV420P);
+    video.set_time_base(Rational(1, 25));
+    video.set_frame_rate(Some(Rational(25, 1)));
+    video.set_max_b_frames(2);
+    video.set_flags(codec::Flags::COPY_OPAQUE);
+    let mut encoder = video.open().unwrap();
+
+    let mut packets = Vec::new();
+    let mut drain = |encoder: &mut encoder::Video| {
+        let mut packet = Packet::empty();
+        while encoder.receive_packet(&mut packet).is_ok() {
+            packets.push(packet.clone());
+        }
+    };
+
+    for pts in 0..8_i64 {
+        let mut frame = frame::Video::new(format::Pixel::YUV420P, 64, 48);
+        frame.set_pts(Some(pts));
+        frame.set_opaque(Some(Arc::new(pts))).unwrap();
+        encoder.send_frame(&frame).unwrap();
+        drain(&mut encoder);
+    }
+    encoder.send_eof().unwrap();
+    drain(&mut encoder);
+
+    assert_eq!(packets.len(), 8);
+    // B-frames come out after the frame they reference.
+    assert!(packets.windows(2).any(|pair| pair[0].pts() > pair[1].pts()));
+    for packet in &mut packets {
+        let pts = *packet.opaque().unwrap().downcast_ref::<i64>().unwrap();
+        assert_eq!(packet.pts(), Some(pts));
+
+        packet
+            .set_opaque(Some(Arc::new(format!("request-{pts}"))))
+            .unwrap();
+    }
+
+    let codec = decoder::find(codec::Id::MPEG1VIDEO).expect("mpeg1video decoder is built in");
+    let mut context = codec::Context::new_with_codec(codec);
+    context.set_flags(codec::Flags::COPY_OPAQUE);
+    let mut decoder = context.decoder().video().unwrap();
+
+    let mut decoded = 0;
+    let mut drain = |decoder: &mut decoder::Video| {
+        let mut frame = frame::Video::empty();
+        while decoder.receive_frame(&mut frame).is_ok() {
+            let request = frame.opaque().unwrap();
+            assert_eq!(
+                request.downcast_ref::<String>(),
+                Some(&format!("request-{}", frame.pts().unwrap()))
+            );
+            decoded += 1;
+        }
+    };
+
+    for packet in &packets {
+        decoder.send_packet(packet).unwrap();
+        drain(&mut decoder);
+    }
+    decoder.send_eof().unwrap();
+    drain(&mut decoder);
+
+    assert_eq!(decoded, 8);
+}
@@ -0,0 +1,58 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::option::{Gettable, Iterable, Settable, Type, Value};
+use crate::ffmpeg::{Rational, codec, encoder};
+
+fn context() -> codec::Context {
+    let codec = encoder::find(codec::Id::MPEG1VIDEO).expect("mpeg1video encoder is built in");
+    codec::Context::new_with_codec(codec)
+}
+
+#[test]
+fn read_back_what_was_set() {
+    let mut context = context();
+
+    context.set_int("b", 1_500_000).unwrap();
+    assert_eq!(context.get_int("b"), Ok(1_500_000));
+    assert_eq!(context.get_str("b").unwrap(), "1500000");
+
+    context.set_rational("aspect", Rational(4, 3)).unwrap();
+    assert_eq!(context.get_rational("aspect"), Ok(Rational(4, 3)));
+
+    context.set_image_size("video_size", 320, 240).unwrap();
+    assert_eq!(context.get_image_size("video_size"), Ok((320, 240)));
+
+    assert!(context.get_int("no_such_option").is_err());
+}
+
+#[test]
+fn private_options_are_searched() {
+    let mut context = context();
+
+    // Belongs to the mpeg1video private class rather than AVCodecContext.
+    context.set_int("scan_offset", 1).unwrap();
+    assert_eq!(context.get_int("scan_offset"), Ok(1));
+}
+
+#[test]
+fn describe_options() {
+    let context = context();
+
+    assert_eq!(context.class().name(), "AVCodecContext");
+
+    let bit_rate = context.options().find(|o| o.name() == "b").unwrap();
+    assert_eq!(bit_rate.kind(), Type::Int64);
+    assert!(bit_rate.help().is_some());
+    assert!(matches!(bit_rate.default_value(), Value::Int(_)));
+    assert!(bit_rate.max() > bit_rate.min());
+
+    let flags = context.options().find(|o| o.name() == "flags").unwrap();
+    assert!(flags.constants().any(|c| c.name() == "qscale"));
+    assert!(flags.constants().all(|c| c.kind() == Type::Constant));
+
+    let private = context
+        .children()
+        .next()
+        .expect("private class of the codec");
+    assert!(private.options().any(|o| o.name() == "scan_offset"));
+}
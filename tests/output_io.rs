@@ -0,0 +1,147 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use crate::ffmpeg::format::context::{Output, StreamIo};
+use crate::ffmpeg::{Error, Packet, codec, format, media};
+
+type Files = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+fn add_video_stream(output: &mut Output, id: codec::Id) {
+    let mut parameters = codec::Parameters::new();
+    parameters.set_medium(media::Type::Video);
+    parameters.set_id(id);
+    parameters.set_width(16);
+    parameters.set_height(16);
+    parameters.set_pixel_format(format::Pixel::BGR24);
+
+    let mut stream = output.add_stream(id).unwrap();
+    stream.set_parameters(parameters);
+    stream.set_time_base((1, 25));
+}
+
+fn write_packets(output: &mut Output, count: i64) {
+    for i in 0..count {
+        let mut packet = Packet::copy(&[i as u8; 8]);
+        packet.set_stream(0);
+        packet.set_pts(Some(i));
+        packet.set_dts(Some(i));
+        packet.write_interleaved(output).unwrap();
+    }
+}
+
+#[test]
+fn nofile_muxer_writes_every_file_through_the_hooks() {
+    let opened = Arc::new(Mutex::new(Vec::new()));
+    let closed: Files = Arc::new(Mutex::new(Vec::new()));
+
+    let mut output = {
+        let opened = opened.clone();
+        let closed = closed.clone();
+
+        format::output_with_io(
+            "frame-%03d.bmp",
+            Some("image2"),
+            move |url| {
+                opened.lock().unwrap().push(url.to_owned());
+                Ok(Cursor::new(Vec::new()))
+            },
+            move |url, cursor: Cursor<Vec<u8>>| {
+                closed
+                    .lock()
+                    .unwrap()
+                    .push((url.to_owned(), cursor.into_inner()));
+            },
+        )
+        .unwrap()
+    };
+
+    add_video_stream(&mut output, codec::Id::BMP);
+    output.write_header().unwrap();
+    write_packets(&mut output, 3);
+    output.write_trailer().unwrap();
+
+    assert_eq!(
+        *opened.lock().unwrap(),
+        ["frame-001.bmp", "frame-002.bmp", "frame-003.bmp"]
+    );
+
+    let closed = closed.lock().unwrap();
+    assert_eq!(closed.len(), 3);
+    for (i, (url, data)) in closed.iter().enumerate() {
+        assert_eq!(url, &format!("frame-{:03}.bmp", i + 1));
+        assert_eq!(data, &vec![i as u8; 8]);
+    }
+}
+
+#[test]
+fn main_file_is_opened_and_closed_through_the_hooks() {
+    let closed: Files = Arc::new(Mutex::new(Vec::new()));
+
+    let mut output = {
+        let closed = closed.clone();
+
+        format::output_with_io(
+            "memory.raw",
+            Some("rawvideo"),
+            |_| StreamIo::from_write_seek(Cursor::new(Vec::new())),
+            move |url, io: StreamIo| {
+                let data = io.into_inner::<Cursor<Vec<u8>>>().unwrap().into_inner();
+                closed.lock().unwrap().push((url.to_owned(), data));
+            },
+        )
+        .unwrap()
+    };
+
+    add_video_stream(&mut output, codec::Id::RAWVIDEO);
+    output.write_header().unwrap();
+    write_packets(&mut output, 2);
+    output.write_trailer().unwrap();
+    assert!(closed.lock().unwrap().is_empty());
+
+    drop(output);
+
+    let closed = closed.lock().unwrap();
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0].0, "memory.raw");
+    assert_eq!(closed[0].1, [[0u8; 8], [1u8; 8]].concat());
+}
+
+#[test]
+fn open_errors_reach_the_muxer() {
+    let result = format::output_with_io(
+        "denied.raw",
+        Some("rawvideo"),
+        |_| -> Result<Cursor<Vec<u8>>, Error> {
+            Err(Error::Other {
+                errno: ffmpeg::util::error::EACCES,
+            })
+        },
+        |_, _| (),
+    );
+
+    assert!(matches!(
+        result,
+        Err(Error::Other {
+            errno: ffmpeg::util::error::EACCES
+        })
+    ));
+}
+
+#[test]
+fn read_contexts_are_rejected() {
+    let result = format::output_with_io(
+        "read.raw",
+        Some("rawvideo"),
+        |_| StreamIo::from_read(Cursor::new(Vec::new())),
+        |_, _| (),
+    );
+
+    assert!(matches!(
+        result,
+        Err(Error::Other {
+            errno: ffmpeg::util::error::EINVAL
+        })
+    ));
+}
@@ -0,0 +1,63 @@
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use crate::common::{encode, mpeg1};
+use crate::ffmpeg::codec::{self, Parser};
+use crate::ffmpeg::{Packet, format, frame, picture};
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 48;
+const FRAMES: usize = 10;
+
+fn encode_frames() -> Vec<Packet> {
+    let frames = (0..FRAMES).map(|i| {
+        let mut frame = frame::Video::new(format::Pixel::YUV420P, WIDTH, HEIGHT);
+        for plane in 0..3 {
+            frame.data_mut(plane).fill((i * 20) as u8);
+        }
+
+        frame
+    });
+
+    encode(&mut mpeg1(WIDTH, HEIGHT, 5, 0), frames)
+}
+
+#[test]
+fn unknown_codec_has_no_parser() {
+    assert!(Parser::new(codec::Id::None).is_none());
+    assert!(Parser::new(codec::Id::H264).is_some());
+}
+
+#[test]
+fn splits_elementary_stream_into_frames() {
+    let encoded = encode_frames();
+    let stream: Vec<u8> = encoded
+        .iter()
+        .flat_map(|packet| packet.data().unwrap().to_vec())
+        .collect();
+
+    let mut parser = Parser::new(codec::Id::MPEG1VIDEO).unwrap();
+    let mut parsed = Vec::new();
+
+    // Deliberately misaligned chunks, as a network transport would deliver.
+    for chunk in stream.chunks(7) {
+        for packet in parser.parse(chunk) {
+            let packet = packet.unwrap();
+
+            if parsed.is_empty() {
+                assert_eq!(parser.kind(), picture::Type::I);
+                assert_eq!(parser.width(), WIDTH);
+                assert_eq!(parser.height(), HEIGHT);
+            }
+
+            parsed.push(packet);
+        }
+    }
+    for packet in parser.flush() {
+        parsed.push(packet.unwrap());
+    }
+
+    assert_eq!(parsed.len(), encoded.len());
+    assert_eq!(parsed.iter().map(Packet::size).sum::<usize>(), stream.len());
+}
@@ -0,0 +1,160 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::{format, frame};
+
+#[test]
+fn rows_hide_stride_padding() {
+    let mut frame = frame::Video::new(format::Pixel::GRAY8, 10, 4);
+    assert!(frame.stride(0) > 10);
+
+    let mut plane = frame.plane_view_mut::<u8>(0);
+    assert_eq!((plane.width(), plane.height()), (10, 4));
+
+    for (y, row) in plane.rows_mut().enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = (y * 10 + x) as u8;
+        }
+    }
+    *plane.pixel_mut(9, 3) = 255;
+
+    let plane = frame.plane_view::<u8>(0);
+    assert_eq!(plane.rows().len(), 4);
+    assert!(plane.rows().all(|row| row.len() == 10));
+    assert_eq!(plane.row(1), &[10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+    assert_eq!(*plane.pixel(3, 2), 23);
+    assert_eq!(*plane.pixel(9, 3), 255);
+    assert_eq!(plane.rows().next_back().unwrap()[0], 30);
+
+    // The padding is left alone.
+    let stride = frame.stride(0);
+    assert_eq!(&frame.data(0)[stride..stride + 10], plane.row(1));
+}
+
+#[test]
+fn sub_rectangles() {
+    let mut frame = frame::Video::new(format::Pixel::GRAY8, 8, 8);
+    frame
+        .plane_view_mut::<u8>(0)
+        .rows_mut()
+        .for_each(|row| row.fill(0));
+
+    let mut plane = frame.plane_view_mut::<u8>(0);
+    let mut view = plane.view_mut(2, 3, 4, 2);
+    assert_eq!((view.width(), view.height()), (4, 2));
+    view.rows_mut().for_each(|row| row.fill(1));
+
+    let inner = plane.view(3, 3, 2, 2);
+    assert_eq!(inner.row(0), &[1, 1]);
+    assert!(plane.view(8, 8, 0, 0).rows().next().is_none());
+
+    let count: usize = frame
+        .plane_view::<u8>(0)
+        .rows()
+        .map(|row| row.iter().filter(|&&p| p == 1).count())
+        .sum();
+    assert_eq!(count, 8);
+    assert_eq!(frame.plane_view::<u8>(0).row(3), &[0, 0, 1, 1, 1, 1, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn sub_rectangle_out_of_bounds() {
+    let frame = frame::Video::new(format::Pixel::GRAY8, 8, 8);
+    frame.plane_view::<u8>(0).view(6, 0, 3, 1);
+}
+
+#[test]
+fn chroma_planes() {
+    let frame = frame::Video::new(format::Pixel::YUV420P, 11, 7);
+
+    let luma = frame.plane_view::<u8>(0);
+    assert_eq!((luma.width(), luma.height()), (11, 7));
+
+    for index in 1..3 {
+        let chroma = frame.plane_view::<u8>(index);
+        assert_eq!((chroma.width(), chroma.height()), (6, 4));
+        assert_eq!(chroma.stride(), frame.stride(index));
+    }
+}
+
+#[test]
+fn packed_pixels() {
+    let mut frame = frame::Video::new(format::Pixel::RGB24, 10, 2);
+
+    let mut plane = frame.plane_view_mut::<[u8; 3]>(0);
+    assert_eq!(plane.width(), 10);
+    plane.rows_mut().for_each(|row| row.fill([1, 2, 3]));
+    *plane.pixel_mut(4, 1) = [7, 8, 9];
+
+    let bytes = frame.plane_view::<u8>(0);
+    assert_eq!(bytes.width(), 30);
+    assert_eq!(&bytes.row(1)[12..15], &[7, 8, 9]);
+    assert_eq!(&bytes.row(0)[27..], &[1, 2, 3]);
+}
+
+#[test]
+#[cfg(target_endian = "little")]
+fn high_bit_depth() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P10LE, 6, 4);
+
+    let mut plane = frame.plane_view_mut::<u16>(1);
+    assert_eq!((plane.width(), plane.height()), (3, 2));
+    *plane.pixel_mut(2, 1) = 1023;
+
+    assert_eq!(*frame.plane_view::<u16>(1).pixel(2, 1), 1023);
+    assert_eq!(frame.plane_view::<u8>(1).width(), 6);
+}
+
+#[test]
+#[should_panic(expected = "unsupported type")]
+fn foreign_byte_order() {
+    let format = if cfg!(target_endian = "big") {
+        format::Pixel::GRAY16LE
+    } else {
+        format::Pixel::GRAY16BE
+    };
+
+    frame::Video::new(format, 4, 4).plane_view::<u16>(0);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn ndarray_views() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 8, 6);
+
+    let mut luma = frame.plane_array_mut::<u8>(0);
+    assert_eq!(luma.dim(), (6, 8));
+    luma.fill(16);
+    luma[[5, 7]] = 235;
+
+    let luma = frame.plane_array::<u8>(0);
+    assert_eq!(luma[[5, 7]], 235);
+    assert!(luma.row(0).iter().all(|&p| p == 16));
+    assert_eq!(frame.plane_array::<u8>(2).dim(), (3, 4));
+
+    let sub = frame.plane_view::<u8>(0).view(4, 2, 4, 4).as_array();
+    assert_eq!(sub[[3, 3]], 235);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn ndarray_packed() {
+    let mut frame = frame::Video::new(format::Pixel::RGBA, 5, 3);
+
+    let mut pixels = frame.packed_array_mut();
+    assert_eq!(pixels.dim(), (3, 5, 4));
+    pixels.fill(0);
+    pixels[[2, 4, 3]] = 255;
+
+    assert_eq!(*frame.plane_view::<[u8; 4]>(0).pixel(4, 2), [0, 0, 0, 255]);
+
+    let frame = frame::Video::new(format::Pixel::RGB24, 10, 2);
+    assert_eq!(frame.packed_array().dim(), (2, 10, 3));
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+#[should_panic(expected = "unsupported format")]
+fn ndarray_packed_planar() {
+    frame::Video::new(format::Pixel::YUV420P, 4, 4).packed_array();
+}
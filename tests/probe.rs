@@ -0,0 +1,71 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use std::io::Cursor;
+
+use crate::ffmpeg::format::context::StreamIo;
+use crate::ffmpeg::{Error, format};
+
+/// A minimal but valid WAV file (PCM s16le, mono, 8 kHz) with `data_len`
+/// bytes of payload.
+fn tiny_wav(data_len: usize) -> Vec<u8> {
+    let mut wav = Vec::with_capacity(44 + data_len);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&8000u32.to_le_bytes()); // sample rate
+    wav.extend_from_slice(&16000u32.to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data_len as u32).to_le_bytes());
+    wav.extend((0..data_len).map(|i| (i % 255) as u8 + 1));
+    wav
+}
+
+#[test]
+fn probes_a_buffer() {
+    let (format, score) = format::probe(&tiny_wav(256), None).unwrap();
+
+    assert_eq!(format.name(), "wav");
+    assert!(score > 25);
+}
+
+#[test]
+fn probes_a_stream_and_opens_it_without_probing_again() {
+    let mut io = StreamIo::from_read(Cursor::new(tiny_wav(4096))).unwrap();
+
+    let (format, _) = format::probe_stream(&mut io, None).unwrap();
+    assert_eq!(format.name(), "wav");
+
+    // The probed bytes are still buffered, so a non-seekable stream works.
+    let input = format::input_from_stream_as(io, format.name(), None).unwrap();
+    assert_eq!(input.format().name(), "wav");
+    assert_eq!(input.nb_streams(), 1);
+}
+
+#[test]
+fn write_contexts_cannot_be_probed() {
+    let mut io = StreamIo::from_write(Vec::new()).unwrap();
+
+    assert!(matches!(
+        format::probe_stream(&mut io, None),
+        Err(Error::Other {
+            errno: ffmpeg::util::error::EINVAL
+        })
+    ));
+}
+
+#[test]
+fn demuxers_are_found_by_name() {
+    assert_eq!(format::find_input("wav").unwrap().name(), "wav");
+    assert!(format::find_input("no-such-demuxer").is_none());
+
+    assert!(matches!(
+        format::input_as("/ffmpeg-next-input-does-not-exist", "no-such-demuxer"),
+        Err(Error::DemuxerNotFound)
+    ));
+}
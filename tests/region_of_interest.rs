@@ -0,0 +1,52 @@
+#![cfg(feature = "ffmpeg_4_2")]
+
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::region_of_interest::{Region, RegionsOfInterest};
+use crate::ffmpeg::{Error, Rational, format, frame};
+
+#[test]
+fn regions_round_trip_through_frames() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 64, 48);
+    assert_eq!(RegionsOfInterest::from_frame(&frame), None);
+
+    let mut roi = RegionsOfInterest::new();
+    roi.add(Region::new(16, 8, 48, 40, Rational(-1, 5)))
+        .add(Region::new(0, 0, 64, 48, Rational(1, 10)));
+    roi.add_to_frame(&mut frame).unwrap();
+
+    let read = RegionsOfInterest::from_frame(&frame).unwrap();
+    assert_eq!(read, roi);
+    assert_eq!(read.regions()[0].qoffset, Rational(-1, 5));
+
+    RegionsOfInterest::new().add_to_frame(&mut frame).unwrap();
+    assert_eq!(RegionsOfInterest::from_frame(&frame), None);
+}
+
+#[test]
+fn invalid_regions_are_rejected() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 64, 48);
+    let einval = Error::Other {
+        errno: libc::EINVAL,
+    };
+
+    let mut valid = RegionsOfInterest::new();
+    valid.add(Region::new(0, 0, 8, 8, Rational(-1, 1)));
+    valid.add_to_frame(&mut frame).unwrap();
+
+    for region in [
+        Region::new(0, 0, 65, 48, Rational(0, 1)),
+        Region::new(0, 0, 64, 49, Rational(0, 1)),
+        Region::new(-1, 0, 8, 8, Rational(0, 1)),
+        Region::new(8, 0, 8, 8, Rational(0, 1)),
+        Region::new(0, 0, 8, 8, Rational(3, 2)),
+        Region::new(0, 0, 8, 8, Rational(0, 0)),
+    ] {
+        let mut roi = RegionsOfInterest::new();
+        roi.add(region);
+        assert_eq!(roi.add_to_frame(&mut frame), Err(einval), "{region:?}");
+    }
+
+    // Failures leave what was there.
+    assert_eq!(RegionsOfInterest::from_frame(&frame), Some(valid));
+}
@@ -0,0 +1,178 @@
+extern crate ffmpeg_next as ffmpeg;
+
+mod common;
+
+use std::time::Duration;
+
+use crate::common::{encode, flat, luma, mpeg1, mux};
+use crate::ffmpeg::format::context::input::SeekMode;
+use crate::ffmpeg::{
+    ChannelLayout, Error, Packet, Rational, Rescale, codec, decoder, encoder, format, frame,
+};
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 48;
+const FRAMES: i64 = 50;
+
+/// `FRAMES` frames of MPEG-1 at 25 fps, with B-frames and a keyframe every
+/// 10 frames, in an in-memory Matroska file.
+fn open() -> (format::context::Input, decoder::Video, Rational) {
+    let mut encoder = mpeg1(WIDTH, HEIGHT, 10, 2);
+    let packets = encode(
+        &mut encoder,
+        (0..FRAMES).map(|i| flat(WIDTH, HEIGHT, luma(i))),
+    );
+    let input = mux("seek.mkv", &encoder, Rational(1, 25), &packets);
+
+    let stream = input.stream(0).unwrap();
+    let time_base = stream.time_base();
+    let decoder = codec::Context::from_parameters(stream.parameters())
+        .unwrap()
+        .decoder()
+        .video()
+        .unwrap();
+
+    (input, decoder, time_base)
+}
+
+fn assert_picture(frame: &frame::Video, i: i64) {
+    let value = frame.data(0)[0];
+    assert!(
+        value.abs_diff(luma(i)) <= 2,
+        "expected frame {i}, got luma {value}"
+    );
+}
+
+#[test]
+fn exact_seek_returns_the_target_frame() {
+    let (mut input, mut decoder, time_base) = open();
+    let mut frame = frame::Video::empty();
+
+    for i in [23i64, 5, 40, 0, 31] {
+        let target = i.rescale(Rational(1, 25), time_base);
+        input
+            .seek_frame(0, target, SeekMode::Exact, &mut decoder, &mut frame)
+            .unwrap();
+
+        assert_eq!(frame.timestamp(), Some(target));
+        assert_picture(&frame, i);
+    }
+}
+
+#[test]
+fn keyframe_seek_returns_the_preceding_keyframe() {
+    let (mut input, mut decoder, time_base) = open();
+    let mut frame = frame::Video::empty();
+
+    let target = 23i64.rescale(Rational(1, 25), time_base);
+    input
+        .seek_frame(0, target, SeekMode::Keyframe, &mut decoder, &mut frame)
+        .unwrap();
+
+    assert!(frame.is_key());
+    assert!(frame.timestamp().unwrap() <= target);
+}
+
+#[test]
+fn seek_by_duration() {
+    let (mut input, mut decoder, _) = open();
+    let mut frame = frame::Video::empty();
+
+    input
+        .seek_frame_at(
+            0,
+            Duration::from_millis(1000),
+            SeekMode::Exact,
+            &mut decoder,
+            &mut frame,
+        )
+        .unwrap();
+
+    assert_picture(&frame, 25);
+}
+
+#[test]
+fn seeking_past_the_end_is_eof() {
+    let (mut input, mut decoder, time_base) = open();
+    let mut frame = frame::Video::empty();
+
+    let target = (FRAMES + 10).rescale(Rational(1, 25), time_base);
+    assert_eq!(
+        input.seek_frame(0, target, SeekMode::Exact, &mut decoder, &mut frame),
+        Err(Error::Eof)
+    );
+    assert_eq!(
+        input.seek_frame(1, 0, SeekMode::Exact, &mut decoder, &mut frame),
+        Err(Error::StreamNotFound)
+    );
+}
+
+/// One second of MP2 at 44.1 kHz, 1152 samples per frame, in an in-memory
+/// Matroska file, whose time base is milliseconds.
+fn mp2_matroska() -> format::context::Input {
+    let codec = encoder::find(codec::Id::MP2).expect("mp2 encoder is built in");
+    let mut audio = codec::Context::new_with_codec(codec)
+        .encoder()
+        .audio()
+        .unwrap();
+    audio.set_rate(44100);
+    audio.set_channel_layout(ChannelLayout::MONO);
+    #[cfg(not(feature = "ffmpeg_7_0"))]
+    audio.set_channels(1);
+    audio.set_format(format::Sample::I16(format::sample::Type::Packed));
+    audio.set_time_base(Rational(1, 44100));
+    audio.set_bit_rate(64_000);
+    let mut encoder = audio.open().unwrap();
+    let samples = encoder.frame_size() as usize;
+
+    let mut packets = Vec::new();
+    let mut drain = |encoder: &mut encoder::Audio| {
+        let mut packet = Packet::empty();
+        while encoder.receive_packet(&mut packet).is_ok() {
+            packets.push(packet.clone());
+        }
+    };
+
+    for i in 0..44100 / samples {
+        let mut frame = frame::Audio::new(
+            format::Sample::I16(format::sample::Type::Packed),
+            samples,
+            ChannelLayout::MONO,
+        );
+        frame.set_rate(44100);
+        frame.plane_mut::<i16>(0).fill(0);
+        frame.set_pts(Some((i * samples) as i64));
+        encoder.send_frame(&frame).unwrap();
+        drain(&mut encoder);
+    }
+    encoder.send_eof().unwrap();
+    drain(&mut encoder);
+
+    mux("seek.mka", &encoder, Rational(1, 44100), &packets)
+}
+
+#[test]
+fn exact_audio_seek_returns_the_frame_containing_the_target() {
+    let mut input = mp2_matroska();
+    let stream = input.stream(0).unwrap();
+    let time_base = stream.time_base();
+    let mut decoder = codec::Context::from_parameters(stream.parameters())
+        .unwrap()
+        .decoder()
+        .audio()
+        .unwrap();
+    let mut frame = frame::Audio::empty();
+
+    // Half way into the eleventh frame.
+    let target = (1152 * 10 + 576i64).rescale(Rational(1, 44100), time_base);
+    input
+        .seek_frame(0, target, SeekMode::Exact, &mut decoder, &mut frame)
+        .unwrap();
+
+    let start = frame.timestamp().unwrap();
+    let end = start + (frame.samples() as i64).rescale(Rational(1, 44100), time_base);
+    assert!(
+        start <= target && target < end,
+        "{target} is not in [{start}, {end})"
+    );
+}
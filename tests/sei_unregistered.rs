@@ -0,0 +1,74 @@
+#![cfg(feature = "ffmpeg_4_4")]
+
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::sei_unregistered::{self, SeiUnregistered};
+use crate::ffmpeg::{Error, Packet, Rational, codec, decoder, encoder, format, frame};
+
+const UUID: [u8; 16] = *b"provenance-id-v1";
+
+#[test]
+fn messages_accumulate_on_frames() {
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    assert_eq!(SeiUnregistered::from_frame(&frame), []);
+
+    let first = SeiUnregistered::new(UUID, b"frame-0001".to_vec());
+    let second = SeiUnregistered::new([0xab; 16], Vec::new());
+    first.add_to_frame(&mut frame).unwrap();
+    second.add_to_frame(&mut frame).unwrap();
+
+    assert_eq!(SeiUnregistered::from_frame(&frame), [first.clone(), second]);
+    assert_eq!(SeiUnregistered::from_bytes(&first.to_bytes()), Some(first));
+    assert_eq!(SeiUnregistered::from_bytes(&UUID[..15]), None);
+}
+
+#[test]
+fn native_encoders_lack_the_option() {
+    let codec = encoder::find(codec::Id::MPEG4).expect("mpeg4 encoder is built in");
+    let mut context = codec::Context::new_with_codec(codec);
+
+    assert_eq!(
+        sei_unregistered::enable(&mut context),
+        Err(Error::OptionNotFound)
+    );
+}
+
+#[test]
+fn round_trips_through_libx264() {
+    let codec = match encoder::find_by_name("libx264") {
+        Some(codec) => codec,
+        None => return,
+    };
+    let mut context = codec::Context::new_with_codec(codec);
+    sei_unregistered::enable(&mut context).unwrap();
+    let mut video = context.encoder().video().unwrap();
+    video.set_width(64);
+    video.set_height(48);
+    video.set_format(format::Pixel::YUV420P);
+    video.set_time_base(Rational(1, 25));
+    let mut encoder = video.open().unwrap();
+
+    let message = SeiUnregistered::new(UUID, b"frame-0001".to_vec());
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 64, 48);
+    frame.set_pts(Some(0));
+    message.add_to_frame(&mut frame).unwrap();
+    encoder.send_frame(&frame).unwrap();
+    encoder.send_eof().unwrap();
+
+    let codec = decoder::find(codec::Id::H264).expect("h264 decoder is built in");
+    let mut decoder = codec::Context::new_with_codec(codec)
+        .decoder()
+        .video()
+        .unwrap();
+    let mut packet = Packet::empty();
+    while encoder.receive_packet(&mut packet).is_ok() {
+        decoder.send_packet(&packet).unwrap();
+    }
+    decoder.send_eof().unwrap();
+
+    let mut decoded = frame::Video::empty();
+    decoder.receive_frame(&mut decoded).unwrap();
+
+    // x264 adds a message of its own, with its version and settings.
+    assert!(SeiUnregistered::from_frame(&decoded).contains(&message));
+}
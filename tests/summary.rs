@@ -0,0 +1,88 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use std::io::Cursor;
+
+use crate::ffmpeg::format::context::StreamIo;
+use crate::ffmpeg::{Dictionary, Rational, codec, format, media};
+
+/// A minimal but valid WAV file (PCM s16le, mono, 8 kHz) holding one second
+/// of silence.
+fn wav() -> Vec<u8> {
+    let data_len = 16000u32;
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&8000u32.to_le_bytes()); // sample rate
+    wav.extend_from_slice(&16000u32.to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+    wav
+}
+
+#[test]
+fn input_summary() {
+    let io = StreamIo::from_read_seek(Cursor::new(wav())).unwrap();
+    let input = format::input_from_stream(io, Some("silence.wav"), None).unwrap();
+    let summary = input.summary(0);
+
+    assert!(!summary.output);
+    assert_eq!(summary.format, "wav");
+    assert_eq!(summary.url, "silence.wav");
+    assert_eq!(summary.duration, Some(1_000_000));
+    assert_eq!(summary.bit_rate, Some(128_000));
+
+    assert_eq!(summary.streams.len(), 1);
+    let stream = &summary.streams[0];
+    assert_eq!(stream.index, 0);
+    assert_eq!(stream.medium, media::Type::Audio);
+    assert_eq!(stream.codec, codec::Id::PCM_S16LE);
+    assert!(stream.description.starts_with("Audio: pcm_s16le"));
+    assert_eq!(stream.time_base, Rational(1, 8000));
+
+    let text = summary.to_string();
+    assert!(text.starts_with("Input #0, wav, from 'silence.wav':\n"));
+    assert!(text.contains("  Duration: 00:00:01.00, start: 0.000000, bitrate: 128 kb/s\n"));
+    assert!(text.contains("  Stream #0:0: Audio: pcm_s16le"));
+}
+
+#[test]
+fn output_summary() {
+    let io = StreamIo::from_write_seek(Cursor::new(Vec::new())).unwrap();
+    let mut output = format::output_to_stream(io, Some("out.mkv"), None).unwrap();
+
+    let mut parameters = codec::Parameters::new();
+    parameters.set_medium(media::Type::Video);
+    parameters.set_id(codec::Id::MPEG1VIDEO);
+    parameters.set_width(64);
+    parameters.set_height(48);
+
+    let mut stream = output.add_stream(codec::Id::MPEG1VIDEO).unwrap();
+    stream.set_parameters(parameters);
+    stream.set_time_base((1, 25));
+    let mut metadata = Dictionary::new();
+    metadata.set("language", "eng");
+    stream.set_metadata(metadata);
+
+    let summary = output.summary(0);
+    assert!(summary.output);
+    assert_eq!(summary.format, "matroska");
+    assert_eq!(summary.streams[0].language(), Some("eng"));
+
+    let text = summary.to_string();
+    assert!(text.starts_with("Output #0, matroska, to 'out.mkv':\n"));
+    assert!(!text.contains("Duration"));
+    assert!(text.contains("  Stream #0:0(eng): Video: mpeg1video"));
+    assert!(text.contains("    Metadata:\n      language        : eng\n"));
+
+    let text = output.summary(2).to_string();
+    assert!(text.starts_with("Output #2, matroska, to 'out.mkv':\n"));
+    assert!(text.contains("  Stream #2:0(eng): Video: mpeg1video"));
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "ffmpeg_4_4")]
+
+extern crate ffmpeg_next as ffmpeg;
+
+use crate::ffmpeg::decoder::ExportData;
+use crate::ffmpeg::video_enc_params::{self, VideoEncParams};
+use crate::ffmpeg::{Packet, Rational, codec, decoder, encoder, format, frame};
+
+#[test]
+fn decoder_exports_block_quantizers() {
+    let codec = encoder::find(codec::Id::MPEG2VIDEO).expect("mpeg2video encoder is built in");
+    let mut video = codec::Context::new_with_codec(codec)
+        .encoder()
+        .video()
+        .unwrap();
+    video.set_width(64);
+    video.set_height(48);
+    video.set_format(format::Pixel::YUV420P);
+    video.set_time_base(Rational(1, 25));
+    video.set_frame_rate(Some(Rational(25, 1)));
+    let mut encoder = video.open().unwrap();
+
+    let codec = decoder::find(codec::Id::MPEG2VIDEO).expect("mpeg2video decoder is built in");
+    let mut context = codec::Context::new_with_codec(codec).decoder();
+    context.set_export_side_data(ExportData::VIDEO_ENC_PARAMS);
+    let mut decoder = context.video().unwrap();
+
+    let mut frame = frame::Video::new(format::Pixel::YUV420P, 64, 48);
+    frame.data_mut(0).fill(100);
+    frame.data_mut(1).fill(128);
+    frame.data_mut(2).fill(128);
+    frame.set_pts(Some(0));
+    encoder.send_frame(&frame).unwrap();
+    encoder.send_eof().unwrap();
+
+    let mut packet = Packet::empty();
+    while encoder.receive_packet(&mut packet).is_ok() {
+        decoder.send_packet(&packet).unwrap();
+    }
+    decoder.send_eof().unwrap();
+
+    let mut decoded = frame::Video::empty();
+    decoder.receive_frame(&mut decoded).unwrap();
+
+    let params = VideoEncParams::from_frame(&decoded).expect("quantizers are exported");
+    assert_eq!(params.kind(), video_enc_params::Type::MPEG2);
+
+    let blocks: Vec<_> = params.blocks().collect();
+    assert_eq!(blocks.len(), 4 * 3);
+    assert_eq!((blocks[5].x, blocks[5].y), (16, 16));
+    for block in blocks {
+        assert_eq!((block.width, block.height), (16, 16));
+        assert!(block.qp > 0);
+    }
+}
+
+#[test]
+fn rejects_truncated_side_data() {
+    assert!(VideoEncParams::from_bytes(&[0; 8]).is_none());
+
+    let frame = frame::Video::new(format::Pixel::YUV420P, 16, 16);
+    assert!(VideoEncParams::from_frame(&frame).is_none());
+}
@@ -0,0 +1,126 @@
+extern crate ffmpeg_next as ffmpeg;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::ffmpeg::{
+    ChannelLayout, Error, Packet,
+    format::{Pixel, Sample, sample::Type},
+    frame,
+};
+
+struct Tracked {
+    data: Vec<u8>,
+    drops: Arc<AtomicUsize>,
+}
+
+impl Tracked {
+    fn new(data: Vec<u8>, drops: &Arc<AtomicUsize>) -> Self {
+        Tracked {
+            data,
+            drops: drops.clone(),
+        }
+    }
+}
+
+impl AsRef<[u8]> for Tracked {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AsMut<[u8]> for Tracked {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn packet_from_vec_shares_the_allocation() {
+    let data = vec![1u8, 2, 3, 4];
+    let ptr = data.as_ptr();
+
+    let mut packet = Packet::from_vec(data).unwrap();
+    assert_eq!(packet.size(), 4);
+    assert_eq!(packet.data(), Some(&[1u8, 2, 3, 4][..]));
+    assert_eq!(packet.data().unwrap().as_ptr(), ptr);
+
+    packet.data_mut().unwrap()[0] = 9;
+    assert_eq!(packet.data(), Some(&[9u8, 2, 3, 4][..]));
+}
+
+#[test]
+fn packet_from_buffer_is_released_with_the_last_reference() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let mut packet = Packet::from_buffer(Tracked::new(vec![5; 16], &drops)).unwrap();
+
+    assert_eq!(packet.data(), Some(&[5u8; 16][..]));
+    // Read-only: callers cannot scribble over memory they gave away.
+    assert!(packet.data_mut().is_none());
+
+    thread::spawn(move || drop(packet)).join().unwrap();
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn video_from_planes_keeps_strides() {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let planes = vec![
+        (Tracked::new(vec![16; 8 * 4], &drops), 8),
+        (Tracked::new(vec![128; 8 * 2], &drops), 8),
+        (Tracked::new(vec![129; 8 * 2], &drops), 8),
+    ];
+    let frame = frame::Video::from_planes(Pixel::YUV420P, 4, 4, planes).unwrap();
+
+    assert_eq!(frame.planes(), 3);
+    assert_eq!(frame.stride(0), 8);
+    assert_eq!(frame.data(0).len(), 8 * 4);
+    assert!(frame.data(1).iter().all(|&b| b == 128));
+    assert!(frame.data(2).iter().all(|&b| b == 129));
+
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(frame);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn video_from_buffer_validates_size() {
+    let strides = [4, 2, 2];
+
+    let frame = frame::Video::from_buffer(Pixel::YUV420P, 4, 4, vec![0u8; 24], &strides).unwrap();
+    assert_eq!(frame.width(), 4);
+    assert_eq!(frame.data(1).len(), 4);
+
+    assert!(matches!(
+        frame::Video::from_buffer(Pixel::YUV420P, 4, 4, vec![0u8; 23], &strides),
+        Err(Error::InvalidData)
+    ));
+    assert!(matches!(
+        frame::Video::from_buffer(Pixel::YUV420P, 4, 4, vec![0u8; 24], &[3, 2, 2]),
+        Err(Error::InvalidData)
+    ));
+}
+
+#[test]
+fn audio_from_buffer_splits_planes() {
+    let mut samples = vec![0f32; 2 * 256];
+    samples[256..].fill(1.0);
+    let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_ne_bytes()).collect();
+
+    let frame =
+        frame::Audio::from_buffer(Sample::F32(Type::Planar), 256, ChannelLayout::STEREO, bytes)
+            .unwrap();
+
+    assert_eq!(frame.planes(), 2);
+    assert_eq!(frame.samples(), 256);
+    assert!(frame.plane::<f32>(0).iter().all(|&s| s == 0.0));
+    assert!(frame.plane::<f32>(1).iter().all(|&s| s == 1.0));
+}